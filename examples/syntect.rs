@@ -15,7 +15,7 @@ use std::fs;
 use std::io;
 
 use argh::FromArgs;
-use syntect::{easy, highlighting, parsing, util};
+use syntect::{highlighting, parsing};
 
 /// Highlight a file and print it using the given method.
 #[derive(FromArgs)]
@@ -29,10 +29,9 @@ struct Args {
     backend: String,
 }
 
-fn render<'a, 's, I>(backend: &str, strings: I)
+fn render<'a, I>(backend: &str, strings: I)
 where
-    's: 'a,
-    I: Iterator<Item = &'a text_style::StyledStr<'s>>,
+    I: Iterator<Item = &'a text_style::StyledString>,
 {
     match backend {
         "ansi_term" => {
@@ -49,7 +48,7 @@ where
             let mut s = cursive::default();
             let mut view = cursive::views::TextView::new("");
             for s in strings {
-                view.append(s);
+                view.append(text_style::StyledStr::from(s));
             }
             s.add_layer(view.scrollable());
             s.add_global_callback('q', |s| s.quit());
@@ -80,15 +79,11 @@ fn main() {
         .find_syntax_for_file(&args.input)
         .expect("Could not read input file")
         .unwrap_or_else(|| ps.find_syntax_plain_text());
-    let mut h = easy::HighlightLines::new(syntax, &ts.themes["base16-ocean.dark"]);
+    let theme = &ts.themes["base16-ocean.dark"];
     let s = fs::read_to_string(&args.input).expect("Could not read input file");
-    let mut lines = Vec::new();
-    for line in util::LinesWithEndings::from(&s) {
-        let ranges = h.highlight(line, &ps);
-        let styled_strs: Vec<text_style::StyledStr<'_>> =
-            ranges.into_iter().map(Into::into).collect();
-        lines.push(styled_strs);
-    }
+    let lines: Vec<_> = text_style::syntect::HighlightedLines::new(&ps, syntax, theme, &s)
+        .merge(true)
+        .collect();
 
     render(&args.backend, lines.iter().flatten());
 }
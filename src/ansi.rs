@@ -0,0 +1,1040 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+//! Parser and renderer for text with ANSI escape sequences.
+//!
+//! This module provides the [`parse`][] and [`try_parse`][] functions that convert a string
+//! containing ANSI escape sequences, for example the colored output of another program, into a
+//! [`StyledText`][].  The spans of the text are styled according to the *Select Graphic
+//! Rendition* (SGR) sequences in the input.  These SGR parameters are supported:
+//!
+//! - `0`: reset all attributes
+//! - `1`, `2`, `3`, `4`, `5`, `6`, `7`, `8`, `9`, `53`: bold, dim, italic, underline, blink (slow
+//!   and rapid), reverse, hidden, strikethrough, overline
+//! - `22`, `23`, `24`, `25`, `27`, `28`, `29`, `55`: disable the effects listed above
+//! - `30`–`37`, `90`–`97`, `40`–`47`, `100`–`107`: 16-color foreground and background
+//! - `38;5;n`, `48;5;n`: 256-color foreground and background (the indices 0–15 are converted to
+//!   ANSI colors)
+//! - `38;2;r;g;b`, `48;2;r;g;b`: truecolor foreground and background
+//! - `39`, `49`: default foreground and background
+//! - `4:0`–`4:5`: underline styles (none, single, double, curly, dotted, dashed)
+//! - `58;5;n`, `58;2;r;g;b`, `59`: underline color and default underline color
+//!
+//! The colon-separated form of the extended colors (`38:5:n`, `38:2::r:g:b`) is supported too.
+//!
+//! Hyperlinks that are set with the *Operating System Command* (OSC) 8 are stored in the
+//! [`link`][`StyledStr::link`] field of the styled strings.
+//!
+//! All other escape sequences and SGR parameters are not supported.  They are removed from the
+//! text by [`parse`][] and reported by [`try_parse`][] and the [`Parser`][] iterator.
+//!
+//! The [`render`][] and [`render_iter`][] functions and the [`Renderer`][] struct write styled
+//! strings using the same escape sequences.  They only write the SGR parameters that are required
+//! to switch from one style to the next one.
+//! The [`render_themed`][] and [`render_iter_themed`][] functions resolve the semantic styles of
+//! the strings using the given [`Theme`][].
+//!
+//! # Examples
+//!
+//! Parsing the output of a program:
+//!
+//! ```
+//! use text_style::{AnsiColor, StyledStr};
+//!
+//! let text = text_style::ansi::parse("\x1b[1;31merror\x1b[0m: not found");
+//! assert_eq!(text.spans(), &[
+//!     StyledStr::plain("error").with(AnsiColor::Red.dark()).bold().into(),
+//!     StyledStr::plain(": not found").into(),
+//! ]);
+//! ```
+//!
+//! Reporting unsupported sequences:
+//!
+//! ```
+//! let result = text_style::ansi::try_parse("\x1b[2Jtest");
+//! let err = result.unwrap_err();
+//! assert_eq!(err.offset, 0);
+//! assert_eq!(err.sequence, "\x1b[2J");
+//! ```
+//!
+//! [`Parser`]: struct.Parser.html
+//! [`Renderer`]: struct.Renderer.html
+//! [`StyledStr::link`]: ../struct.StyledStr.html#structfield.link
+//! [`StyledText`]: ../struct.StyledText.html
+//! [`parse`]: fn.parse.html
+//! [`render`]: fn.render.html
+//! [`render_iter`]: fn.render_iter.html
+//! [`render_themed`]: fn.render_themed.html
+//! [`render_iter_themed`]: fn.render_iter_themed.html
+//! [`Theme`]: ../theme/struct.Theme.html
+//! [`try_parse`]: fn.try_parse.html
+
+use std::error;
+use std::fmt;
+use std::io;
+use std::iter;
+
+use crate::theme::Theme;
+use crate::{
+    palette, AnsiColor, AnsiMode, Color, Effect, Hyperlink, Style, StyledStr, StyledString,
+    StyledText, UnderlineStyle, ANSI_COLORS,
+};
+
+const ESC: char = '\x1b';
+
+/// An error that occured during parsing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Error {
+    /// The kind of the error.
+    pub kind: ErrorKind,
+    /// The byte offset of the escape sequence in the input string.
+    pub offset: usize,
+    /// The escape sequence that caused the error.
+    pub sequence: String,
+}
+
+/// The kind of a parser [`Error`][].
+///
+/// [`Error`]: struct.Error.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ErrorKind {
+    /// The escape sequence is not a SGR sequence or is incomplete.
+    UnsupportedSequence,
+    /// The SGR sequence contains parameters that are not supported or invalid.  All supported
+    /// parameters of this sequence have been applied.
+    UnsupportedParameter,
+}
+
+/// An iterator over the styled strings in a string with ANSI escape sequences.
+///
+/// This iterator yields an error for every unsupported escape sequence and continues with the
+/// next sequence.
+///
+/// # Example
+///
+/// ```
+/// use text_style::ansi;
+///
+/// let mut parser = ansi::Parser::new("\x1b[3mtest\x1b[21m");
+/// assert_eq!(parser.next(), Some(Ok(text_style::StyledStr::plain("test").italic())));
+/// assert!(parser.next().unwrap().is_err());
+/// assert_eq!(parser.next(), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Parser<'a> {
+    s: &'a str,
+    offset: usize,
+    style: Style,
+    link: Option<Hyperlink<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    /// Creates a new parser for the given string.
+    pub fn new(s: &'a str) -> Parser<'a> {
+        Parser {
+            s,
+            offset: 0,
+            style: Style::default(),
+            link: None,
+        }
+    }
+
+    /// Returns the current style of this parser, i. e. the style that will be used for the next
+    /// string.
+    pub fn style(&self) -> Style {
+        self.style
+    }
+
+    /// Returns the current hyperlink of this parser, i. e. the hyperlink that will be used for the
+    /// next string.
+    pub fn link(&self) -> Option<&Hyperlink<'a>> {
+        self.link.as_ref()
+    }
+
+    fn parse_sequence(&mut self) -> Result<(), Error> {
+        let start = self.offset;
+        let rest = &self.s[start..];
+        let (len, result) = match rest[1..].chars().next() {
+            Some('[') => {
+                let len = csi_len(rest);
+                let result = match get_sgr_params(&rest[..len]) {
+                    Some(params) => {
+                        if apply_sgr(&mut self.style, params) {
+                            Ok(())
+                        } else {
+                            Err(ErrorKind::UnsupportedParameter)
+                        }
+                    }
+                    None => Err(ErrorKind::UnsupportedSequence),
+                };
+                (len, result)
+            }
+            Some(']') => {
+                let len = osc_len(rest);
+                let s = self.s;
+                let result = match get_hyperlink(&s[start..start + len]) {
+                    Some(link) => {
+                        self.link = link;
+                        Ok(())
+                    }
+                    None => Err(ErrorKind::UnsupportedSequence),
+                };
+                (len, result)
+            }
+            Some(c) => (1 + c.len_utf8(), Err(ErrorKind::UnsupportedSequence)),
+            None => (1, Err(ErrorKind::UnsupportedSequence)),
+        };
+        self.offset += len;
+        result.map_err(|kind| Error {
+            kind,
+            offset: start,
+            sequence: self.s[start..start + len].to_owned(),
+        })
+    }
+}
+
+impl<'a> Iterator for Parser<'a> {
+    type Item = Result<StyledStr<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.offset < self.s.len() {
+            let rest = &self.s[self.offset..];
+            if rest.starts_with(ESC) {
+                if let Err(err) = self.parse_sequence() {
+                    return Some(Err(err));
+                }
+            } else {
+                let len = rest.find(ESC).unwrap_or(rest.len());
+                self.offset += len;
+                let style = if self.style == Style::default() {
+                    None
+                } else {
+                    Some(self.style)
+                };
+                let mut s = StyledStr::new(&rest[..len], style);
+                s.link = self.link.clone();
+                return Some(Ok(s));
+            }
+        }
+        None
+    }
+}
+
+impl Error {
+    fn description(&self) -> &'static str {
+        match self.kind {
+            ErrorKind::UnsupportedSequence => "Unsupported escape sequence",
+            ErrorKind::UnsupportedParameter => "Unsupported SGR parameter",
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {:?} at offset {}",
+            self.description(),
+            self.sequence,
+            self.offset
+        )
+    }
+}
+
+impl error::Error for Error {}
+
+/// Parses a string with ANSI escape sequences into a styled text, ignoring unsupported escape
+/// sequences.
+///
+/// # Example
+///
+/// ```
+/// use text_style::{Color, StyledStr};
+///
+/// let text = text_style::ansi::parse("\x1b[38;5;208mtest\x1b[m");
+/// assert_eq!(text.spans(), &[StyledStr::plain("test").with(Color::Indexed(208)).into()]);
+/// ```
+pub fn parse(s: &str) -> StyledText {
+    Parser::new(s).filter_map(Result::ok).collect()
+}
+
+/// Parses a string with ANSI escape sequences into a styled text, returning an error for the first
+/// unsupported escape sequence.
+///
+/// # Example
+///
+/// ```
+/// use text_style::{Color, StyledStr};
+///
+/// let text = text_style::ansi::try_parse("\x1b[38;2;255;136;0mtest\x1b[m")
+///     .expect("Failed to parse string");
+/// assert_eq!(text.spans(), &[StyledStr::plain("test").with(Color::Rgb {
+///     r: 255,
+///     g: 136,
+///     b: 0,
+/// }).into()]);
+/// ```
+pub fn try_parse(s: &str) -> Result<StyledText, Error> {
+    Parser::new(s)
+        .map(|result| result.map(StyledString::from))
+        .collect()
+}
+
+/// Returns the length of the CSI sequence at the start of the given string.
+fn csi_len(s: &str) -> usize {
+    // skip ESC and [, then look for the final byte in the range 0x40–0x7e
+    s.bytes()
+        .skip(2)
+        .position(|b| (0x40..=0x7e).contains(&b))
+        .map(|i| i + 3)
+        .unwrap_or(s.len())
+}
+
+/// Returns the length of the OSC sequence at the start of the given string.
+fn osc_len(s: &str) -> usize {
+    // the sequence is terminated either by BEL or by ST (ESC \)
+    let bytes = s.as_bytes();
+    (2..bytes.len())
+        .find_map(|i| match (bytes[i], bytes.get(i + 1)) {
+            (0x07, _) => Some(i + 1),
+            (0x1b, Some(b'\\')) => Some(i + 2),
+            _ => None,
+        })
+        .unwrap_or(s.len())
+}
+
+/// Returns the hyperlink set by the given OSC sequence if it is an OSC 8 sequence.
+///
+/// The outer option is `None` if the sequence is not a valid OSC 8 sequence.  The inner option is
+/// `None` if the sequence closes the current hyperlink.
+fn get_hyperlink(seq: &str) -> Option<Option<Hyperlink<'_>>> {
+    let seq = seq.get(2..)?;
+    let seq = seq
+        .strip_suffix('\x07')
+        .or_else(|| seq.strip_suffix("\x1b\\"))?;
+    let mut parts = seq.splitn(3, ';');
+    if parts.next()? != "8" {
+        return None;
+    }
+    let params = parts.next()?;
+    let url = parts.next()?;
+    if url.is_empty() {
+        Some(None)
+    } else {
+        let mut link = Hyperlink::new(url);
+        // the parameters are colon-separated key=value pairs, only the id key is defined
+        link.id = params
+            .split(':')
+            .find_map(|param| param.strip_prefix("id="))
+            .map(From::from);
+        Some(Some(link))
+    }
+}
+
+/// Returns the parameters of the given CSI sequence if it is a SGR sequence.
+fn get_sgr_params(seq: &str) -> Option<&str> {
+    let params = seq.get(2..)?.strip_suffix('m')?;
+    if params
+        .bytes()
+        .all(|b| b.is_ascii_digit() || b == b';' || b == b':')
+    {
+        Some(params)
+    } else {
+        None
+    }
+}
+
+/// Applies the given SGR parameters to the style and returns whether all parameters are
+/// supported.
+fn apply_sgr(style: &mut Style, params: &str) -> bool {
+    let mut supported = true;
+    let mut groups = params.split(';');
+    while let Some(group) = groups.next() {
+        let mut parts = group.split(':');
+        // an empty parameter means 0, but a parameter that cannot be parsed must not reset the
+        // style
+        let param = match parts.next().and_then(parse_param) {
+            Some(param) => param,
+            None => {
+                supported = false;
+                continue;
+            }
+        };
+        let is_supported = match param {
+            0 => {
+                *style = Style::default();
+                true
+            }
+            1 => {
+                style.set_bold(true);
+                true
+            }
+            2 => {
+                style.set_dim(true);
+                true
+            }
+            3 => {
+                style.set_italic(true);
+                true
+            }
+            4 => {
+                // the colon syntax selects the underline style, 4:0 disables the underline
+                let underline_style = match parts.next().map(parse_param) {
+                    None | Some(Some(1)) => Ok(None),
+                    Some(Some(2)) => Ok(Some(UnderlineStyle::Double)),
+                    Some(Some(3)) => Ok(Some(UnderlineStyle::Curly)),
+                    Some(Some(4)) => Ok(Some(UnderlineStyle::Dotted)),
+                    Some(Some(5)) => Ok(Some(UnderlineStyle::Dashed)),
+                    Some(Some(0)) => Err(true),
+                    _ => Err(false),
+                };
+                match underline_style {
+                    Ok(underline_style) => {
+                        style.set_underline(true);
+                        style.underline_style = underline_style;
+                        true
+                    }
+                    Err(is_supported) => {
+                        if is_supported {
+                            style.set_underline(false);
+                            style.underline_style = None;
+                        }
+                        is_supported
+                    }
+                }
+            }
+            5 | 6 => {
+                style.set_blink(true);
+                true
+            }
+            7 => {
+                style.set_reverse(true);
+                true
+            }
+            8 => {
+                style.set_hidden(true);
+                true
+            }
+            9 => {
+                style.strikethrough(true);
+                true
+            }
+            22 => {
+                style.set_bold(false);
+                style.set_dim(false);
+                true
+            }
+            23 => {
+                style.set_italic(false);
+                true
+            }
+            24 => {
+                style.set_underline(false);
+                style.underline_style = None;
+                true
+            }
+            25 => {
+                style.set_blink(false);
+                true
+            }
+            27 => {
+                style.set_reverse(false);
+                true
+            }
+            28 => {
+                style.set_hidden(false);
+                true
+            }
+            29 => {
+                style.strikethrough(false);
+                true
+            }
+            30..=37 => {
+                style.fg = Some(get_ansi_color(param - 30).dark());
+                true
+            }
+            38 => match parse_extended_color(parts, &mut groups) {
+                Some(color) => {
+                    style.fg = Some(color);
+                    true
+                }
+                None => false,
+            },
+            39 => {
+                style.fg = None;
+                true
+            }
+            40..=47 => {
+                style.bg = Some(get_ansi_color(param - 40).dark());
+                true
+            }
+            48 => match parse_extended_color(parts, &mut groups) {
+                Some(color) => {
+                    style.bg = Some(color);
+                    true
+                }
+                None => false,
+            },
+            49 => {
+                style.bg = None;
+                true
+            }
+            53 => {
+                style.set_overline(true);
+                true
+            }
+            55 => {
+                style.set_overline(false);
+                true
+            }
+            58 => match parse_extended_color(parts, &mut groups) {
+                Some(color) => {
+                    style.underline_color = Some(color);
+                    true
+                }
+                None => false,
+            },
+            59 => {
+                style.underline_color = None;
+                true
+            }
+            90..=97 => {
+                style.fg = Some(get_ansi_color(param - 90).light());
+                true
+            }
+            100..=107 => {
+                style.bg = Some(get_ansi_color(param - 100).light());
+                true
+            }
+            _ => false,
+        };
+        supported = supported && is_supported;
+    }
+    supported
+}
+
+fn parse_param(s: &str) -> Option<u16> {
+    if s.is_empty() {
+        Some(0)
+    } else {
+        s.parse().ok()
+    }
+}
+
+/// Parses the arguments of an extended color (SGR 38 and 48).
+///
+/// The arguments are either stored in the colon-separated sub-parameters of the current group or,
+/// if there are no sub-parameters, in the following semicolon-separated groups.
+fn parse_extended_color<'a>(
+    mut parts: impl Iterator<Item = &'a str>,
+    groups: &mut impl Iterator<Item = &'a str>,
+) -> Option<Color> {
+    if let Some(mode) = parts.next() {
+        let args: Vec<_> = parts.collect();
+        match parse_param(mode)? {
+            5 if args.len() == 1 => Some(get_indexed_color(parse_u8(args[0])?)),
+            // the first argument of the colon syntax is an optional color space identifier
+            2 if args.len() == 3 || args.len() == 4 => {
+                let args = &args[args.len() - 3..];
+                Some(Color::Rgb {
+                    r: parse_u8(args[0])?,
+                    g: parse_u8(args[1])?,
+                    b: parse_u8(args[2])?,
+                })
+            }
+            _ => None,
+        }
+    } else {
+        match parse_param(groups.next()?)? {
+            5 => Some(get_indexed_color(parse_u8(groups.next()?)?)),
+            2 => Some(Color::Rgb {
+                r: parse_u8(groups.next()?)?,
+                g: parse_u8(groups.next()?)?,
+                b: parse_u8(groups.next()?)?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+fn parse_u8(s: &str) -> Option<u8> {
+    parse_param(s).and_then(|n| if n <= 255 { Some(n as u8) } else { None })
+}
+
+/// Converts a color from the 256-color palette, using the ANSI colors for the indices 0–15.
+fn get_indexed_color(n: u8) -> Color {
+    palette::indexed_ansi(n).unwrap_or(Color::Indexed(n))
+}
+
+fn get_ansi_color(n: u16) -> AnsiColor {
+    ANSI_COLORS[usize::from(n)]
+}
+
+/// A renderer that writes styled strings with ANSI escape sequences to an output.
+///
+/// The renderer keeps track of the current style of the output and only writes the SGR parameters
+/// that are required to switch from the style of one string to the style of the next string.
+/// After writing the last string, the style has to be reset by calling [`finish`][].
+///
+/// # Example
+///
+/// ```
+/// use text_style::{ansi, StyledStr};
+///
+/// let mut renderer = ansi::Renderer::new(Vec::new());
+/// renderer.render(StyledStr::plain("bold").bold())?;
+/// renderer.render(StyledStr::plain(" italic").bold().italic())?;
+/// renderer.render(StyledStr::plain(" plain"))?;
+/// let output = renderer.finish()?;
+/// assert_eq!(output, b"\x1b[1mbold\x1b[3m italic\x1b[0m plain");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+///
+/// [`finish`]: #method.finish
+#[derive(Clone, Debug)]
+pub struct Renderer<W: io::Write> {
+    w: W,
+    state: OutputState,
+}
+
+impl<W: io::Write> Renderer<W> {
+    /// Creates a new renderer for the given output.
+    ///
+    /// The renderer assumes that the output currently uses the default style.
+    pub fn new(w: W) -> Renderer<W> {
+        Renderer {
+            w,
+            state: OutputState::default(),
+        }
+    }
+
+    /// Writes the given styled string to the output, preceded by the escape sequences that are
+    /// required to switch to its style and hyperlink.
+    pub fn render<'a>(&mut self, s: impl Into<StyledStr<'a>>) -> io::Result<()> {
+        self.state.render(&mut self.w, s.into(), write_transition)
+    }
+
+    /// Resets the style and the hyperlink of the output and returns the output.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.state.finish(&mut self.w, write_transition)?;
+        Ok(self.w)
+    }
+}
+
+/// Writes the SGR sequence for the given transition.
+fn write_transition(w: &mut impl io::Write, transition: Transition) -> io::Result<()> {
+    let mut params = Vec::new();
+    if transition.reset {
+        params.push("0".to_owned());
+    }
+    params.extend(transition.changes.into_iter().map(get_change_param));
+    write!(w, "\x1b[{}m", params.join(";"))
+}
+
+/// The style and the hyperlink of an output that styled strings are written to.
+///
+/// This struct is used by the renderers that only write the changes between the styles of
+/// consecutive strings.  It writes the OSC 8 sequences for the hyperlinks and determines the
+/// style transitions, but the transitions are written by the renderers.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct OutputState {
+    style: Style,
+    link: Option<Hyperlink<'static>>,
+}
+
+impl OutputState {
+    /// Writes the given styled string to the output, preceded by the sequences that are required
+    /// to switch to its hyperlink and its style.  Empty strings are skipped.
+    pub fn render<W: io::Write>(
+        &mut self,
+        w: &mut W,
+        s: StyledStr<'_>,
+        write_transition: impl FnOnce(&mut W, Transition) -> io::Result<()>,
+    ) -> io::Result<()> {
+        if s.s.is_empty() {
+            return Ok(());
+        }
+        if self.link.as_ref() != s.link.as_ref() {
+            if self.link.is_some() {
+                w.write_all(HYPERLINK_END_SEQUENCE.as_bytes())?;
+            }
+            if let Some(link) = &s.link {
+                w.write_all(hyperlink_start_sequence(link).as_bytes())?;
+            }
+            self.link = s.link.map(Hyperlink::into_owned);
+        }
+        self.set_style(w, s.style.unwrap_or_default(), write_transition)?;
+        w.write_all(s.s.as_bytes())
+    }
+
+    /// Resets the style and closes the hyperlink of the output.
+    pub fn finish<W: io::Write>(
+        &mut self,
+        w: &mut W,
+        write_transition: impl FnOnce(&mut W, Transition) -> io::Result<()>,
+    ) -> io::Result<()> {
+        self.set_style(w, Style::default(), write_transition)?;
+        if self.link.take().is_some() {
+            w.write_all(HYPERLINK_END_SEQUENCE.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn set_style<W: io::Write>(
+        &mut self,
+        w: &mut W,
+        style: Style,
+        write_transition: impl FnOnce(&mut W, Transition) -> io::Result<()>,
+    ) -> io::Result<()> {
+        let transition = get_minimal_transition(&self.style, &style);
+        self.style = style;
+        match transition {
+            Some(transition) => write_transition(w, transition),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Renders a styled string to the given output using ANSI escape sequences.
+///
+/// # Example
+///
+/// ```
+/// let s = text_style::StyledStr::plain("test").bold();
+/// text_style::ansi::render(std::io::stdout(), s)
+///     .expect("Failed to render string");
+/// ```
+pub fn render<'a>(w: impl io::Write, s: impl Into<StyledStr<'a>>) -> io::Result<()> {
+    render_iter(w, iter::once(s))
+}
+
+/// Renders multiple styled string to the given output using ANSI escape sequences.
+///
+/// This function uses a [`Renderer`][] to only write the changes between the styles of
+/// consecutive strings.
+///
+/// # Example
+///
+/// ```
+/// let v = vec![
+///     text_style::StyledStr::plain("test").bold(),
+///     text_style::StyledStr::plain(" "),
+///     text_style::StyledStr::plain("test2").italic(),
+/// ];
+/// text_style::ansi::render_iter(std::io::stdout(), v.iter())
+///     .expect("Failed to render string");
+/// ```
+///
+/// [`Renderer`]: struct.Renderer.html
+pub fn render_iter<'a, I, Iter, S, W>(w: W, iter: I) -> io::Result<()>
+where
+    I: IntoIterator<Item = S, IntoIter = Iter>,
+    Iter: Iterator<Item = S>,
+    S: Into<StyledStr<'a>>,
+    W: io::Write,
+{
+    let mut renderer = Renderer::new(w);
+    for s in iter {
+        renderer.render(s)?;
+    }
+    renderer.finish().map(|_| ())
+}
+
+/// Renders a styled string to the given output using ANSI escape sequences, resolving its semantic
+/// style with the given theme.
+///
+/// See [`Theme::apply`][] for more information.
+///
+/// # Example
+///
+/// ```
+/// use text_style::{theme::Theme, AnsiColor, Style, StyledStr};
+///
+/// let theme = Theme::new().with("error", Style::fg(AnsiColor::Red.light()));
+/// let s = StyledStr::themed("failed", "error");
+/// text_style::ansi::render_themed(std::io::stdout(), s, &theme)
+///     .expect("Failed to render string");
+/// ```
+///
+/// [`Theme::apply`]: ../theme/struct.Theme.html#method.apply
+pub fn render_themed<'a>(
+    w: impl io::Write,
+    s: impl Into<StyledStr<'a>>,
+    theme: &Theme,
+) -> io::Result<()> {
+    render(w, theme.apply(s))
+}
+
+/// Renders multiple styled strings to the given output using ANSI escape sequences, resolving their
+/// semantic styles with the given theme.
+///
+/// See [`Theme::apply`][] for more information.
+///
+/// # Example
+///
+/// ```
+/// use text_style::{theme::Theme, AnsiColor, Style, StyledStr};
+///
+/// let theme = Theme::new().with("error", Style::fg(AnsiColor::Red.light()));
+/// let v = vec![
+///     StyledStr::themed("error", "error"),
+///     StyledStr::plain(": failed"),
+/// ];
+/// text_style::ansi::render_iter_themed(std::io::stdout(), v.iter(), &theme)
+///     .expect("Failed to render string");
+/// ```
+///
+/// [`Theme::apply`]: ../theme/struct.Theme.html#method.apply
+pub fn render_iter_themed<'a, I, Iter, S, W>(w: W, iter: I, theme: &Theme) -> io::Result<()>
+where
+    I: IntoIterator<Item = S, IntoIter = Iter>,
+    Iter: Iterator<Item = S>,
+    S: Into<StyledStr<'a>>,
+    W: io::Write,
+{
+    render_iter(w, iter.into_iter().map(|s| theme.apply(s)))
+}
+
+/// A change of a style attribute that is required to switch from one style to another style, see
+/// [`get_transition`](fn.get_transition.html).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum Change {
+    /// Disables the bold and the dim effect.
+    NormalIntensity,
+    /// Enables the given effect.  The underline effect is set with `Underline` instead.
+    EffectOn(Effect),
+    /// Disables the given effect.  The bold and dim effects are disabled with `NormalIntensity`
+    /// instead.
+    EffectOff(Effect),
+    /// Enables the underline effect with the given style.
+    Underline(UnderlineStyle),
+    /// Sets the foreground color, or resets it if it is `None`.
+    Fg(Option<Color>),
+    /// Sets the background color, or resets it if it is `None`.
+    Bg(Option<Color>),
+    /// Sets the underline color, or resets it if it is `None`.
+    UnderlineColor(Option<Color>),
+}
+
+/// Returns the changes that are required to switch from the style `from` to the style `to`.
+///
+/// Attributes that are not visible, for example the underline style if the underline effect is
+/// disabled, are ignored.
+pub(crate) fn get_transition(from: &Style, to: &Style) -> Vec<Change> {
+    let mut changes = Vec::new();
+    let mut effects = to.effects;
+
+    // bold and dim share the same parameter for disabling them
+    let is_intensity_reset = (from.effects.is_bold && !to.effects.is_bold)
+        || (from.effects.is_dim && !to.effects.is_dim);
+    if is_intensity_reset {
+        changes.push(Change::NormalIntensity);
+    }
+    for effect in from.effects {
+        if !to.effects.is_set(effect) {
+            if effect != Effect::Bold && effect != Effect::Dim {
+                changes.push(Change::EffectOff(effect));
+            }
+        } else if !(is_intensity_reset && (effect == Effect::Bold || effect == Effect::Dim)) {
+            effects.set(effect, false);
+        }
+    }
+    if from.effects.is_underline && from.get_underline_style() != to.get_underline_style() {
+        effects.set(Effect::Underline, to.effects.is_underline);
+    }
+
+    for effect in effects {
+        changes.push(match effect {
+            Effect::Underline => {
+                Change::Underline(to.get_underline_style().unwrap_or(UnderlineStyle::Single))
+            }
+            _ => Change::EffectOn(effect),
+        });
+    }
+
+    if from.fg != to.fg {
+        changes.push(Change::Fg(to.fg));
+    }
+    if from.bg != to.bg {
+        changes.push(Change::Bg(to.bg));
+    }
+    if from.underline_color != to.underline_color {
+        changes.push(Change::UnderlineColor(to.underline_color));
+    }
+    changes
+}
+
+/// A transition from one style to another style, see
+/// [`get_minimal_transition`](fn.get_minimal_transition.html).
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Transition {
+    /// Whether all attributes are reset before applying the changes.
+    pub reset: bool,
+    /// The changes that are applied after the optional reset.
+    pub changes: Vec<Change>,
+}
+
+/// Returns the shortest transition from the style `from` to the style `to`, or `None` if no
+/// attributes have to be written.
+///
+/// The style is reset if `to` does not set any attributes or if the reset and the changes from the
+/// default style require fewer SGR parameters than the changes from `from`, for example if most
+/// effects are disabled.
+pub(crate) fn get_minimal_transition(from: &Style, to: &Style) -> Option<Transition> {
+    let changes = get_transition(from, to);
+    if changes.is_empty() {
+        // the styles only differ in attributes that are not written, for example the underline
+        // style if the underline effect is disabled
+        return None;
+    }
+    let reset_changes = get_transition(&Style::default(), to);
+    let params_len = |changes: &[Change]| -> usize {
+        changes
+            .iter()
+            .map(|change| get_change_param(*change).len() + 1)
+            .sum()
+    };
+    // the reset requires the additional parameter "0;"
+    if reset_changes.is_empty() || params_len(&reset_changes) + 2 < params_len(&changes) {
+        Some(Transition {
+            reset: true,
+            changes: reset_changes,
+        })
+    } else {
+        Some(Transition {
+            reset: false,
+            changes,
+        })
+    }
+}
+
+/// Returns the SGR parameter for the given change.
+fn get_change_param(change: Change) -> String {
+    match change {
+        Change::NormalIntensity => "22".to_owned(),
+        Change::EffectOn(effect) => get_effect_on_param(effect).to_owned(),
+        Change::EffectOff(effect) => get_effect_off_param(effect).to_owned(),
+        Change::Underline(underline_style) => underline_style_param(underline_style).to_owned(),
+        Change::Fg(color) => color.map_or_else(|| "39".to_owned(), |c| get_color_param(c, 30)),
+        Change::Bg(color) => color.map_or_else(|| "49".to_owned(), |c| get_color_param(c, 40)),
+        Change::UnderlineColor(color) => {
+            color.map_or_else(|| "59".to_owned(), get_underline_color_param)
+        }
+    }
+}
+
+fn get_effect_on_param(effect: Effect) -> &'static str {
+    match effect {
+        Effect::Bold => "1",
+        Effect::Dim => "2",
+        Effect::Italic => "3",
+        Effect::Underline => "4",
+        Effect::Blink => "5",
+        Effect::Reverse => "7",
+        Effect::Hidden => "8",
+        Effect::Strikethrough => "9",
+        Effect::Overline => "53",
+    }
+}
+
+fn get_effect_off_param(effect: Effect) -> &'static str {
+    match effect {
+        // bold and dim share the same parameter
+        Effect::Bold | Effect::Dim => "22",
+        Effect::Italic => "23",
+        Effect::Underline => "24",
+        Effect::Blink => "25",
+        Effect::Reverse => "27",
+        Effect::Hidden => "28",
+        Effect::Strikethrough => "29",
+        Effect::Overline => "55",
+    }
+}
+
+/// Returns the SGR parameters for the given foreground (`base = 30`) or background (`base = 40`)
+/// color.
+fn get_color_param(color: Color, base: u8) -> String {
+    match color {
+        Color::Ansi { color, mode } => {
+            let base = if mode == AnsiMode::Light {
+                base + 60
+            } else {
+                base
+            };
+            (base + color.index()).to_string()
+        }
+        Color::Indexed(index) => format!("{};5;{}", base + 8, index),
+        Color::Rgb { r, g, b } => format!("{};2;{};{};{}", base + 8, r, g, b),
+    }
+}
+
+fn get_underline_color_param(color: Color) -> String {
+    match color {
+        Color::Ansi { color, mode } => {
+            let offset = if mode == AnsiMode::Light { 8 } else { 0 };
+            format!("58;5;{}", color.index() + offset)
+        }
+        Color::Indexed(index) => format!("58;5;{}", index),
+        Color::Rgb { r, g, b } => format!("58;2;{};{};{}", r, g, b),
+    }
+}
+
+fn underline_style_param(underline_style: UnderlineStyle) -> &'static str {
+    match underline_style {
+        UnderlineStyle::Single => "4",
+        UnderlineStyle::Double => "4:2",
+        UnderlineStyle::Curly => "4:3",
+        UnderlineStyle::Dotted => "4:4",
+        UnderlineStyle::Dashed => "4:5",
+    }
+}
+
+/// Returns the OSC 8 sequence that starts the given hyperlink.
+///
+/// Control characters in the URL are percent-encoded so that they cannot terminate the sequence.
+/// Control characters, colons and semicolons are removed from the ID as they are not allowed in
+/// the parameters of the sequence.
+pub(crate) fn hyperlink_start_sequence(link: &Hyperlink<'_>) -> String {
+    let mut url = String::with_capacity(link.url.len());
+    for c in link.url.chars() {
+        if c.is_control() {
+            let mut buf = [0; 4];
+            for b in c.encode_utf8(&mut buf).bytes() {
+                url.push_str(&format!("%{:02X}", b));
+            }
+        } else {
+            url.push(c);
+        }
+    }
+    match &link.id {
+        Some(id) => {
+            let id: String = id
+                .chars()
+                .filter(|c| !c.is_control() && *c != ':' && *c != ';')
+                .collect();
+            format!("\x1b]8;id={};{}\x1b\\", id, url)
+        }
+        None => format!("\x1b]8;;{}\x1b\\", url),
+    }
+}
+
+/// The OSC 8 sequence that ends a hyperlink.
+pub(crate) const HYPERLINK_END_SEQUENCE: &str = "\x1b]8;;\x1b\\";
+
+/// Returns the SGR sequence that sets the given underline style.
+#[cfg(any(feature = "crossterm", feature = "termion"))]
+pub(crate) fn underline_style_sequence(underline_style: UnderlineStyle) -> String {
+    format!("\x1b[{}m", underline_style_param(underline_style))
+}
+
+/// Returns the SGR sequence for the given change.
+#[cfg(any(feature = "crossterm", feature = "termion"))]
+pub(crate) fn change_sequence(change: Change) -> String {
+    format!("\x1b[{}m", get_change_param(change))
+}
+
+/// Returns the SGR sequence that sets the given underline color.
+#[cfg(any(feature = "crossterm", feature = "termion"))]
+pub(crate) fn underline_color_sequence(color: Color) -> String {
+    format!("\x1b[{}m", get_underline_color_param(color))
+}
@@ -9,9 +9,24 @@
 //! - [`Color`][] to [`ansi_term::Color`][]
 //! - [`Style`][] to [`ansi_term::Style`][]
 //! - [`StyledStr`][] and [`StyledString`][] to [`ansi_term::ANSIString`][]
+//! - [`ansi_term::Color`][] to [`Color`][]
+//! - [`ansi_term::Style`][] to [`Style`][]
+//! - [`ansi_term::ANSIString`][] to [`StyledStr`][] and [`StyledString`][]
+//!
+//! `ansi_term` does not support the [`Overline`][] effect and underline styles and colors, so they
+//! are ignored by this module.  All attributes of `ansi_term`’s types are supported, so the
+//! conversions from `ansi_term`’s types are lossless.  Hyperlinks are not supported by `ansi_term`
+//! either.  They are ignored by the conversion methods, but the render methods write the
+//! corresponding OSC 8 sequences.
 //!
 //! It also provides the [`render`][] and [`render_iter`][] methods to render strings and iterators
-//! over strings.
+//! over strings.  The [`render_with`][] and [`render_iter_with`][] methods convert the strings to
+//! the given terminal [`Capabilities`][] before rendering them.  [`render`][] and [`render_iter`][]
+//! write all attributes regardless of the output, so use [`render_with`][] with
+//! [`Capabilities::from_env`][] to respect the terminal and the `NO_COLOR` and `CLICOLOR`
+//! environment variables.
+//! The [`render_themed`][] and [`render_iter_themed`][] methods resolve the semantic styles of
+//! the strings using the given [`Theme`][].
 //!
 //! # Examples
 //!
@@ -35,20 +50,44 @@
 //!     .expect("Failed to render string");
 //! ```
 //!
+//! Rendering a [`StyledText`][]:
+//!
+//! ```
+//! let mut text = text_style::StyledText::new();
+//! text.push(text_style::StyledStr::plain("test").bold());
+//! text.push(text_style::StyledStr::plain(" test2").italic());
+//! text_style::ansi_term::render_iter(std::io::stdout(), &text)
+//!     .expect("Failed to render text");
+//! ```
+//!
 //! [`ansi_term`]: https://docs.rs/ansi_term
 //! [`ansi_term::ANSIString`]: https://docs.rs/ansi_term/latest/ansi_term/type.ANSIString.html
 //! [`ansi_term::Color`]: https://docs.rs/ansi_term/latest/ansi_term/enum.Color.html
 //! [`ansi_term::Style`]: https://docs.rs/ansi_term/latest/ansi_term/struct.Style.html
 //! [`Color`]: ../enum.Color.html
+//! [`Overline`]: ../enum.Effect.html#variant.Overline
 //! [`Style`]: ../struct.Style.html
 //! [`StyledStr`]: ../struct.StyledStr.html
 //! [`StyledString`]: ../struct.StyledString.html
+//! [`StyledText`]: ../struct.StyledText.html
 //! [`render`]: fn.render.html
 //! [`render_iter`]: fn.render_iter.html
+//! [`render_with`]: fn.render_with.html
+//! [`render_iter_with`]: fn.render_iter_with.html
+//! [`render_themed`]: fn.render_themed.html
+//! [`render_iter_themed`]: fn.render_iter_themed.html
+//! [`Theme`]: ../theme/struct.Theme.html
+//! [`Capabilities`]: ../terminal/struct.Capabilities.html
+//! [`Capabilities::from_env`]: ../terminal/struct.Capabilities.html#method.from_env
 
+use std::fmt;
 use std::io;
 
-use crate::{AnsiColor, AnsiMode, Color, Style, StyledStr, StyledString};
+use crate::terminal::Capabilities;
+use crate::theme::Theme;
+use crate::{
+    ansi, palette, AnsiColor, AnsiMode, Color, Effects, Hyperlink, Style, StyledStr, StyledString,
+};
 
 impl From<Color> for ansi_term::Color {
     fn from(color: Color) -> ansi_term::Color {
@@ -57,6 +96,7 @@ impl From<Color> for ansi_term::Color {
                 AnsiMode::Dark => get_dark_color(color),
                 AnsiMode::Light => get_light_color(color),
             },
+            Color::Indexed(index) => ansi_term::Color::Fixed(index),
             Color::Rgb { r, g, b } => ansi_term::Color::RGB(r, g, b),
         }
     }
@@ -97,7 +137,10 @@ impl From<Style> for ansi_term::Style {
             is_italic: style.effects.is_italic,
             is_underline: style.effects.is_underline,
             is_strikethrough: style.effects.is_strikethrough,
-            ..Default::default()
+            is_dimmed: style.effects.is_dim,
+            is_reverse: style.effects.is_reverse,
+            is_blink: style.effects.is_blink,
+            is_hidden: style.effects.is_hidden,
         }
     }
 }
@@ -126,6 +169,66 @@ impl<'a> From<StyledString> for ansi_term::ANSIString<'a> {
     }
 }
 
+impl From<ansi_term::Color> for Color {
+    fn from(color: ansi_term::Color) -> Color {
+        match color {
+            ansi_term::Color::Black => AnsiColor::Black.dark(),
+            ansi_term::Color::Red => AnsiColor::Red.dark(),
+            ansi_term::Color::Green => AnsiColor::Green.dark(),
+            ansi_term::Color::Yellow => AnsiColor::Yellow.dark(),
+            ansi_term::Color::Blue => AnsiColor::Blue.dark(),
+            ansi_term::Color::Purple => AnsiColor::Magenta.dark(),
+            ansi_term::Color::Cyan => AnsiColor::Cyan.dark(),
+            ansi_term::Color::White => AnsiColor::White.dark(),
+            ansi_term::Color::Fixed(index) => {
+                palette::indexed_ansi(index).unwrap_or(Color::Indexed(index))
+            }
+            ansi_term::Color::RGB(r, g, b) => Color::Rgb { r, g, b },
+        }
+    }
+}
+
+impl From<ansi_term::Style> for Style {
+    fn from(style: ansi_term::Style) -> Style {
+        Style {
+            fg: style.foreground.map(Into::into),
+            bg: style.background.map(Into::into),
+            effects: Effects {
+                is_bold: style.is_bold,
+                is_italic: style.is_italic,
+                is_underline: style.is_underline,
+                is_strikethrough: style.is_strikethrough,
+                is_dim: style.is_dimmed,
+                is_reverse: style.is_reverse,
+                is_blink: style.is_blink,
+                is_hidden: style.is_hidden,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+}
+
+impl<'a, 'b> From<&'b ansi_term::ANSIString<'a>> for StyledStr<'b> {
+    fn from(s: &'b ansi_term::ANSIString<'a>) -> StyledStr<'b> {
+        StyledStr::new(s, get_style(*s.style_ref()))
+    }
+}
+
+impl<'a> From<ansi_term::ANSIString<'a>> for StyledString {
+    fn from(s: ansi_term::ANSIString<'a>) -> StyledString {
+        StyledString::new(str::to_owned(&s), get_style(*s.style_ref()))
+    }
+}
+
+fn get_style(style: ansi_term::Style) -> Option<Style> {
+    if style.is_plain() {
+        None
+    } else {
+        Some(style.into())
+    }
+}
+
 /// Renders a styled string to the given output using `ansi_term`.
 ///
 /// # Example
@@ -136,12 +239,14 @@ impl<'a> From<StyledString> for ansi_term::ANSIString<'a> {
 ///     .expect("Failed to render string");
 /// ```
 pub fn render<'a>(mut w: impl io::Write, s: impl Into<StyledStr<'a>>) -> io::Result<()> {
-    write!(w, "{}", ansi_term::ANSIString::from(s.into()))
+    let s = s.into();
+    write_linked(&mut w, s.link.as_ref(), ansi_term::ANSIString::from(&s))
 }
 
 /// Renders multiple styled string to the given output using `ansi_term`.
 ///
 /// This function uses [`ansi_term::ANSIStrings`][] to minimize the written control sequences.
+/// Consecutive strings with the same hyperlink are written as one link.
 ///
 /// # Example
 ///
@@ -163,10 +268,154 @@ where
     S: Into<StyledStr<'a>>,
     W: io::Write,
 {
-    let strings: Vec<_> = iter
-        .into_iter()
-        .map(Into::into)
-        .map(ansi_term::ANSIString::from)
-        .collect();
-    write!(w, "{}", ansi_term::ANSIStrings(&strings))
+    let spans: Vec<StyledStr<'a>> = iter.into_iter().map(Into::into).collect();
+    let mut start = 0;
+    while start < spans.len() {
+        let link = spans[start].link.as_ref();
+        let len = spans[start..]
+            .iter()
+            .take_while(|s| s.link.as_ref() == link)
+            .count();
+        let strings: Vec<_> = spans[start..start + len]
+            .iter()
+            .map(ansi_term::ANSIString::from)
+            .collect();
+        write_linked(&mut w, link, ansi_term::ANSIStrings(&strings))?;
+        start += len;
+    }
+    Ok(())
+}
+
+fn write_linked(
+    mut w: impl io::Write,
+    link: Option<&Hyperlink<'_>>,
+    s: impl fmt::Display,
+) -> io::Result<()> {
+    match link {
+        Some(link) => write!(
+            w,
+            "{}{}{}",
+            ansi::hyperlink_start_sequence(link),
+            s,
+            ansi::HYPERLINK_END_SEQUENCE
+        ),
+        None => write!(w, "{}", s),
+    }
+}
+
+/// Renders a styled string to the given output using `ansi_term`, converting it to the given
+/// terminal capabilities.
+///
+/// See [`Capabilities::apply`][] for more information.
+///
+/// # Example
+///
+/// ```
+/// use text_style::terminal::Capabilities;
+///
+/// let s = text_style::StyledStr::plain("test").bold();
+/// text_style::ansi_term::render_with(std::io::stdout(), s, &Capabilities::from_env(false))
+///     .expect("Failed to render string");
+/// ```
+///
+/// [`Capabilities::apply`]: ../terminal/struct.Capabilities.html#method.apply
+pub fn render_with<'a>(
+    w: impl io::Write,
+    s: impl Into<StyledStr<'a>>,
+    capabilities: &Capabilities,
+) -> io::Result<()> {
+    render(w, capabilities.apply(s))
+}
+
+/// Renders multiple styled string to the given output using `ansi_term`, converting them to the
+/// given terminal capabilities.
+///
+/// See [`Capabilities::apply`][] for more information.
+///
+/// # Example
+///
+/// ```
+/// use text_style::terminal::Capabilities;
+///
+/// let v = vec![
+///     text_style::StyledStr::plain("test").bold(),
+///     text_style::StyledStr::plain(" "),
+///     text_style::StyledStr::plain("test2").italic(),
+/// ];
+/// text_style::ansi_term::render_iter_with(
+///     std::io::stdout(),
+///     v.iter(),
+///     &Capabilities::from_env(false),
+/// )
+/// .expect("Failed to render string");
+/// ```
+///
+/// [`Capabilities::apply`]: ../terminal/struct.Capabilities.html#method.apply
+pub fn render_iter_with<'a, I, Iter, S, W>(
+    w: W,
+    iter: I,
+    capabilities: &Capabilities,
+) -> io::Result<()>
+where
+    I: IntoIterator<Item = S, IntoIter = Iter>,
+    Iter: Iterator<Item = S>,
+    S: Into<StyledStr<'a>>,
+    W: io::Write,
+{
+    render_iter(w, iter.into_iter().map(|s| capabilities.apply(s)))
+}
+
+/// Renders a styled string to the given output using `ansi_term`, resolving its semantic style with
+/// the given theme.
+///
+/// See [`Theme::apply`][] for more information.
+///
+/// # Example
+///
+/// ```
+/// use text_style::{theme::Theme, AnsiColor, Style, StyledStr};
+///
+/// let theme = Theme::new().with("error", Style::fg(AnsiColor::Red.light()));
+/// let s = StyledStr::themed("failed", "error");
+/// text_style::ansi_term::render_themed(std::io::stdout(), s, &theme)
+///     .expect("Failed to render string");
+/// ```
+///
+/// [`Theme::apply`]: ../theme/struct.Theme.html#method.apply
+pub fn render_themed<'a>(
+    w: impl io::Write,
+    s: impl Into<StyledStr<'a>>,
+    theme: &Theme,
+) -> io::Result<()> {
+    render(w, theme.apply(s))
+}
+
+/// Renders multiple styled strings to the given output using `ansi_term`, resolving their semantic
+/// styles with the given theme.
+///
+/// See [`Theme::apply`][] for more information.
+///
+/// # Example
+///
+/// ```
+/// use text_style::{theme::Theme, AnsiColor, Style, StyledStr};
+///
+/// let theme = Theme::new().with("error", Style::fg(AnsiColor::Red.light()));
+/// let v = vec![
+///     StyledStr::themed("error", "error"),
+///     StyledStr::plain(": failed"),
+/// ];
+/// text_style::ansi_term::render_iter_themed(std::io::stdout(), v.iter(), &theme)
+///     .expect("Failed to render string");
+/// ```
+///
+/// [`Theme::apply`]: ../theme/struct.Theme.html#method.apply
+pub fn render_iter_themed<'a, I, Iter, S, W>(w: W, iter: I, theme: &Theme) -> io::Result<()>
+where
+    I: IntoIterator<Item = S, IntoIter = Iter>,
+    Iter: Iterator<Item = S>,
+    S: Into<StyledStr<'a>>,
+    W: io::Write,
+{
+    render_iter(w, iter.into_iter().map(|s| theme.apply(s)))
 }
@@ -12,8 +12,28 @@
 //! - [`Style`][] to [`crossterm::style::ContentStyle`][]
 //! - [`StyledStr`][] and [`StyledString`][] to [`crossterm::style::StyledContent`][]
 //!
+//! The underline style and color of a [`Style`][] and hyperlinks are not supported by
+//! `crossterm`'s types.  They are ignored by the conversion methods, but the render methods write
+//! the corresponding SGR and OSC 8 sequences.
+//!
+//! It also implements `TryFrom` for these conversions:
+//! - [`crossterm::style::Attributes`][] to [`Effects`][]
+//! - [`crossterm::style::ContentStyle`][] to [`Style`][]
+//! - [`crossterm::style::StyledContent`][] to [`StyledString`][]
+//!
+//! The reset color is converted to `None`, and the rapid blink attribute is converted to the
+//! [`Blink`][] effect.  All other attributes that are not supported by this crate, for example
+//! the `Fraktur` attribute or attributes that unset an effect, are reported in the
+//! [`ConversionError`][].
+//!
 //! It also provides the [`render`][] and [`render_iter`][] methods to render strings and iterators
-//! over strings.
+//! over strings.  The [`render_with`][] and [`render_iter_with`][] methods convert the strings to
+//! the given terminal [`Capabilities`][] before rendering them.  [`render`][] and [`render_iter`][]
+//! write all attributes regardless of the output, so use [`render_with`][] with
+//! [`Capabilities::from_env`][] to respect the terminal and the `NO_COLOR` and `CLICOLOR`
+//! environment variables.
+//! The [`render_themed`][] and [`render_iter_themed`][] methods resolve the semantic styles of
+//! the strings using the given [`Theme`][].
 //!
 //! # Examples
 //!
@@ -37,26 +57,54 @@
 //!     .expect("Failed to render string");
 //! ```
 //!
+//! Rendering a [`StyledText`][]:
+//!
+//! ```
+//! let mut text = text_style::StyledText::new();
+//! text.push(text_style::StyledStr::plain("test").bold());
+//! text.push(text_style::StyledStr::plain(" test2").italic());
+//! text_style::crossterm::render_iter(std::io::stdout(), &text)
+//!     .expect("Failed to render text");
+//! ```
+//!
 //! [`crossterm`]: https://docs.rs/crossterm
 //! [`crossterm::style::Attribute`]: https://docs.rs/crossterm/latest/crossterm/style/enum.Attribute.html
 //! [`crossterm::style::Attributes`]: https://docs.rs/crossterm/latest/crossterm/style/struct.Attributes.html
 //! [`crossterm::style::Color`]: https://docs.rs/crossterm/latest/crossterm/style/enum.Color.html
 //! [`crossterm::style::ContentStyle`]: https://docs.rs/crossterm/latest/crossterm/style/struct.ContentStyle.html
 //! [`crossterm::style::StyledContent`]: https://docs.rs/crossterm/latest/crossterm/style/struct.StyledContent.html
+//! [`Blink`]: ../enum.Effect.html#variant.Blink
 //! [`Color`]: ../enum.Color.html
+//! [`ConversionError`]: ../struct.ConversionError.html
 //! [`Effect`]: ../enum.Effect.html
 //! [`Effects`]: ../struct.Effects.html
 //! [`Style`]: ../struct.Style.html
 //! [`StyledStr`]: ../struct.StyledStr.html
 //! [`StyledString`]: ../struct.StyledString.html
+//! [`StyledText`]: ../struct.StyledText.html
 //! [`render`]: fn.render.html
 //! [`render_iter`]: fn.render_iter.html
+//! [`render_with`]: fn.render_with.html
+//! [`render_iter_with`]: fn.render_iter_with.html
+//! [`render_themed`]: fn.render_themed.html
+//! [`render_iter_themed`]: fn.render_iter_themed.html
+//! [`Theme`]: ../theme/struct.Theme.html
+//! [`Capabilities`]: ../terminal/struct.Capabilities.html
+//! [`Capabilities::from_env`]: ../terminal/struct.Capabilities.html#method.from_env
 
+use std::convert::TryFrom;
+use std::fmt;
 use std::io;
 
 use crossterm::style;
 
-use crate::{AnsiColor, AnsiMode, Color, Effect, Effects, Style, StyledStr, StyledString};
+use crate::ansi::{self, Change, Transition};
+use crate::terminal::Capabilities;
+use crate::theme::Theme;
+use crate::{
+    palette, AnsiColor, AnsiMode, Color, ConversionError, Effect, Effects, Style, StyledStr,
+    StyledString, UnderlineStyle,
+};
 
 impl From<Color> for style::Color {
     fn from(color: Color) -> style::Color {
@@ -83,6 +131,7 @@ impl From<Color> for style::Color {
                 (Light, Cyan) => style::Color::Cyan,
                 (Light, White) => style::Color::White,
             },
+            Color::Indexed(index) => style::Color::AnsiValue(index),
             Color::Rgb { r, g, b } => style::Color::Rgb { r, g, b },
         }
     }
@@ -95,6 +144,11 @@ impl From<Effect> for style::Attribute {
             Effect::Italic => style::Attribute::Italic,
             Effect::Underline => style::Attribute::Underlined,
             Effect::Strikethrough => style::Attribute::CrossedOut,
+            Effect::Dim => style::Attribute::Dim,
+            Effect::Reverse => style::Attribute::Reverse,
+            Effect::Blink => style::Attribute::SlowBlink,
+            Effect::Hidden => style::Attribute::Hidden,
+            Effect::Overline => style::Attribute::OverLined,
         }
     }
 }
@@ -137,6 +191,111 @@ impl From<StyledString> for style::StyledContent<String> {
     }
 }
 
+impl TryFrom<style::Attributes> for Effects {
+    type Error = ConversionError<Effects>;
+
+    fn try_from(attributes: style::Attributes) -> Result<Effects, ConversionError<Effects>> {
+        let mut effects = Effects::new();
+        let mut unsupported = Vec::new();
+        for attribute in style::Attribute::iterator().filter(|a| attributes.has(*a)) {
+            let effect = match attribute {
+                style::Attribute::Bold => Effect::Bold,
+                style::Attribute::Italic => Effect::Italic,
+                style::Attribute::Underlined => Effect::Underline,
+                style::Attribute::CrossedOut => Effect::Strikethrough,
+                style::Attribute::Dim => Effect::Dim,
+                style::Attribute::Reverse => Effect::Reverse,
+                style::Attribute::SlowBlink | style::Attribute::RapidBlink => Effect::Blink,
+                style::Attribute::Hidden => Effect::Hidden,
+                style::Attribute::OverLined => Effect::Overline,
+                _ => {
+                    unsupported.push(format!("{:?}", attribute));
+                    continue;
+                }
+            };
+            effects.set(effect, true);
+        }
+        ConversionError::check(effects, unsupported)
+    }
+}
+
+impl TryFrom<style::ContentStyle> for Style {
+    type Error = ConversionError<Style>;
+
+    fn try_from(style: style::ContentStyle) -> Result<Style, ConversionError<Style>> {
+        let mut unsupported = Vec::new();
+        let effects =
+            ConversionError::unwrap_into(Effects::try_from(style.attributes), &mut unsupported);
+        let style = Style {
+            fg: style.foreground_color.and_then(get_color),
+            bg: style.background_color.and_then(get_color),
+            effects,
+            ..Default::default()
+        };
+        ConversionError::check(style, unsupported)
+    }
+}
+
+impl<'a, D: fmt::Display> TryFrom<&'a style::StyledContent<D>> for StyledString {
+    type Error = ConversionError<StyledString>;
+
+    fn try_from(
+        content: &'a style::StyledContent<D>,
+    ) -> Result<StyledString, ConversionError<StyledString>> {
+        let s = content.content().to_string();
+        match Style::try_from(*content.style()) {
+            Ok(style) => Ok(StyledString::new(s, get_style(style))),
+            Err(err) => Err(err.map(|style| StyledString::new(s, get_style(style)))),
+        }
+    }
+}
+
+impl<D: fmt::Display> TryFrom<style::StyledContent<D>> for StyledString {
+    type Error = ConversionError<StyledString>;
+
+    fn try_from(
+        content: style::StyledContent<D>,
+    ) -> Result<StyledString, ConversionError<StyledString>> {
+        StyledString::try_from(&content)
+    }
+}
+
+/// Converts a `crossterm` color to a color, returning `None` for the default color.
+fn get_color(color: style::Color) -> Option<Color> {
+    let color = match color {
+        style::Color::Reset => return None,
+        style::Color::Black => AnsiColor::Black.dark(),
+        style::Color::DarkRed => AnsiColor::Red.dark(),
+        style::Color::DarkGreen => AnsiColor::Green.dark(),
+        style::Color::DarkYellow => AnsiColor::Yellow.dark(),
+        style::Color::DarkBlue => AnsiColor::Blue.dark(),
+        style::Color::DarkMagenta => AnsiColor::Magenta.dark(),
+        style::Color::DarkCyan => AnsiColor::Cyan.dark(),
+        style::Color::Grey => AnsiColor::White.dark(),
+        style::Color::DarkGrey => AnsiColor::Black.light(),
+        style::Color::Red => AnsiColor::Red.light(),
+        style::Color::Green => AnsiColor::Green.light(),
+        style::Color::Yellow => AnsiColor::Yellow.light(),
+        style::Color::Blue => AnsiColor::Blue.light(),
+        style::Color::Magenta => AnsiColor::Magenta.light(),
+        style::Color::Cyan => AnsiColor::Cyan.light(),
+        style::Color::White => AnsiColor::White.light(),
+        style::Color::AnsiValue(index) => {
+            palette::indexed_ansi(index).unwrap_or(Color::Indexed(index))
+        }
+        style::Color::Rgb { r, g, b } => Color::Rgb { r, g, b },
+    };
+    Some(color)
+}
+
+fn get_style(style: Style) -> Option<Style> {
+    if style == Style::default() {
+        None
+    } else {
+        Some(style)
+    }
+}
+
 /// Renders a styled string to the given output using `crossterm`.
 ///
 /// # Example
@@ -147,15 +306,15 @@ impl From<StyledString> for style::StyledContent<String> {
 ///     .expect("Failed to render string");
 /// ```
 pub fn render<'a>(mut w: impl io::Write, s: impl Into<StyledStr<'a>>) -> crossterm::Result<()> {
-    use crossterm::ExecutableCommand;
-
-    w.execute(crossterm::style::PrintStyledContent(s.into().into()))
-        .map(|_| {})
+    queue(&mut w, s.into())?;
+    w.flush()
 }
 
 /// Renders multiple styled string to the given output using `crossterm`.
 ///
-/// This function queues the draw commands, so the output has to be flushed by the caller.
+/// This function only queues the commands for the attributes that change between consecutive
+/// strings, or a reset if it is shorter, and resets the style after the last string.  The output
+/// is not flushed, so it has to be flushed by the caller.
 ///
 /// # Example
 ///
@@ -175,10 +334,224 @@ where
     S: Into<StyledStr<'a>>,
     W: io::Write,
 {
+    let mut state = ansi::OutputState::default();
+    for s in iter {
+        state.render(&mut w, s.into(), queue_transition)?;
+    }
+    state.finish(&mut w, queue_transition)
+}
+
+/// Queues the commands for the given style transition.
+fn queue_transition(w: &mut impl io::Write, transition: Transition) -> crossterm::Result<()> {
     use crossterm::QueueableCommand;
 
-    for s in iter {
-        w.queue(crossterm::style::PrintStyledContent(s.into().into()))?;
+    if transition.reset {
+        w.queue(style::SetAttribute(style::Attribute::Reset))?;
+    }
+    for change in transition.changes {
+        match change {
+            Change::NormalIntensity => {
+                w.queue(style::SetAttribute(style::Attribute::NormalIntensity))?;
+            }
+            Change::EffectOn(effect) => {
+                w.queue(style::SetAttribute(effect.into()))?;
+            }
+            Change::EffectOff(effect) => {
+                w.queue(style::SetAttribute(get_off_attribute(effect)))?;
+            }
+            Change::Underline(UnderlineStyle::Single) => {
+                w.queue(style::SetAttribute(style::Attribute::Underlined))?;
+            }
+            Change::Fg(color) => {
+                w.queue(style::SetForegroundColor(
+                    color.map_or(style::Color::Reset, Into::into),
+                ))?;
+            }
+            Change::Bg(color) => {
+                w.queue(style::SetBackgroundColor(
+                    color.map_or(style::Color::Reset, Into::into),
+                ))?;
+            }
+            // crossterm does not support underline styles and colors
+            Change::Underline(_) | Change::UnderlineColor(_) => {
+                w.queue(style::Print(ansi::change_sequence(change)))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn get_off_attribute(effect: Effect) -> style::Attribute {
+    match effect {
+        Effect::Bold | Effect::Dim => style::Attribute::NormalIntensity,
+        Effect::Italic => style::Attribute::NoItalic,
+        Effect::Underline => style::Attribute::NoUnderline,
+        Effect::Strikethrough => style::Attribute::NotCrossedOut,
+        Effect::Reverse => style::Attribute::NoReverse,
+        Effect::Blink => style::Attribute::NoBlink,
+        Effect::Hidden => style::Attribute::NoHidden,
+        Effect::Overline => style::Attribute::NotOverLined,
+    }
+}
+
+/// Queues the commands to print the given string.
+///
+/// crossterm does not support underline styles and colors and hyperlinks, so we have to write the
+/// SGR and OSC 8 sequences ourselves.
+fn queue(w: &mut impl io::Write, mut s: StyledStr<'_>) -> crossterm::Result<()> {
+    use crossterm::QueueableCommand;
+
+    let link = s.link.take();
+    if let Some(link) = &link {
+        w.queue(style::Print(ansi::hyperlink_start_sequence(link)))?;
+    }
+
+    let style = s.style.unwrap_or_default();
+    let underline_style = style
+        .get_underline_style()
+        .filter(|underline_style| *underline_style != UnderlineStyle::Single);
+    let mut content = style::StyledContent::from(s);
+
+    if let Some(underline_style) = underline_style {
+        // the SGR sequence for the underline style replaces the plain underline attribute
+        content
+            .style_mut()
+            .attributes
+            .unset(style::Attribute::Underlined);
+        w.queue(style::Print(ansi::underline_style_sequence(
+            underline_style,
+        )))?;
+    }
+    if let Some(color) = style.underline_color {
+        w.queue(style::Print(ansi::underline_color_sequence(color)))?;
+    }
+    // crossterm only resets the style if attributes are set
+    let needs_reset = (underline_style.is_some() || style.underline_color.is_some())
+        && content.style().attributes.is_empty();
+
+    w.queue(style::PrintStyledContent(content))?;
+    if needs_reset {
+        w.queue(style::SetAttribute(style::Attribute::Reset))?;
+    }
+    if link.is_some() {
+        w.queue(style::Print(ansi::HYPERLINK_END_SEQUENCE))?;
     }
     Ok(())
 }
+
+/// Renders a styled string to the given output using `crossterm`, converting it to the given
+/// terminal capabilities.
+///
+/// See [`Capabilities::apply`][] for more information.
+///
+/// # Example
+///
+/// ```
+/// use text_style::terminal::Capabilities;
+///
+/// let s = text_style::StyledStr::plain("test").bold();
+/// text_style::crossterm::render_with(std::io::stdout(), s, &Capabilities::from_env(false))
+///     .expect("Failed to render string");
+/// ```
+///
+/// [`Capabilities::apply`]: ../terminal/struct.Capabilities.html#method.apply
+pub fn render_with<'a>(
+    w: impl io::Write,
+    s: impl Into<StyledStr<'a>>,
+    capabilities: &Capabilities,
+) -> crossterm::Result<()> {
+    render(w, capabilities.apply(s))
+}
+
+/// Renders multiple styled string to the given output using `crossterm`, converting them to the
+/// given terminal capabilities.
+///
+/// See [`Capabilities::apply`][] for more information.
+///
+/// # Example
+///
+/// ```
+/// use text_style::terminal::Capabilities;
+///
+/// let v = vec![
+///     text_style::StyledStr::plain("test").bold(),
+///     text_style::StyledStr::plain(" "),
+///     text_style::StyledStr::plain("test2").italic(),
+/// ];
+/// text_style::crossterm::render_iter_with(
+///     std::io::stdout(),
+///     v.iter(),
+///     &Capabilities::from_env(false),
+/// )
+/// .expect("Failed to render string");
+/// ```
+///
+/// [`Capabilities::apply`]: ../terminal/struct.Capabilities.html#method.apply
+pub fn render_iter_with<'a, I, Iter, S, W>(
+    w: W,
+    iter: I,
+    capabilities: &Capabilities,
+) -> crossterm::Result<()>
+where
+    I: IntoIterator<Item = S, IntoIter = Iter>,
+    Iter: Iterator<Item = S>,
+    S: Into<StyledStr<'a>>,
+    W: io::Write,
+{
+    render_iter(w, iter.into_iter().map(|s| capabilities.apply(s)))
+}
+
+/// Renders a styled string to the given output using `crossterm`, resolving its semantic style with
+/// the given theme.
+///
+/// See [`Theme::apply`][] for more information.
+///
+/// # Example
+///
+/// ```
+/// use text_style::{theme::Theme, AnsiColor, Style, StyledStr};
+///
+/// let theme = Theme::new().with("error", Style::fg(AnsiColor::Red.light()));
+/// let s = StyledStr::themed("failed", "error");
+/// text_style::crossterm::render_themed(std::io::stdout(), s, &theme)
+///     .expect("Failed to render string");
+/// ```
+///
+/// [`Theme::apply`]: ../theme/struct.Theme.html#method.apply
+pub fn render_themed<'a>(
+    w: impl io::Write,
+    s: impl Into<StyledStr<'a>>,
+    theme: &Theme,
+) -> crossterm::Result<()> {
+    render(w, theme.apply(s))
+}
+
+/// Renders multiple styled strings to the given output using `crossterm`, resolving their semantic
+/// styles with the given theme.
+///
+/// See [`Theme::apply`][] for more information.
+///
+/// # Example
+///
+/// ```
+/// use text_style::{theme::Theme, AnsiColor, Style, StyledStr};
+///
+/// let theme = Theme::new().with("error", Style::fg(AnsiColor::Red.light()));
+/// let v = vec![
+///     StyledStr::themed("error", "error"),
+///     StyledStr::plain(": failed"),
+/// ];
+/// text_style::crossterm::render_iter_themed(std::io::stdout(), v.iter(), &theme)
+///     .expect("Failed to render string");
+/// ```
+///
+/// [`Theme::apply`]: ../theme/struct.Theme.html#method.apply
+pub fn render_iter_themed<'a, I, Iter, S, W>(w: W, iter: I, theme: &Theme) -> crossterm::Result<()>
+where
+    I: IntoIterator<Item = S, IntoIter = Iter>,
+    Iter: Iterator<Item = S>,
+    S: Into<StyledStr<'a>>,
+    W: io::Write,
+{
+    render_iter(w, iter.into_iter().map(|s| theme.apply(s)))
+}
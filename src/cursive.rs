@@ -11,6 +11,21 @@
 //! - [`Effect`][] to [`cursive::theme::Effect`][]
 //! - [`Style`][] to [`cursive::theme::Style`][]
 //! - [`StyledStr`][] and [`StyledString`][] to [`cursive::utils::markup::StyledString`][]
+//! - [`StyledText`][] to [`cursive::utils::markup::StyledString`][]
+//! - [`cursive::theme::BaseColor`][] to [`AnsiColor`][]
+//!
+//! `cursive` does not support the [`Dim`][], [`Hidden`][] and [`Overline`][] effects.  They are
+//! converted to [`cursive::theme::Effect::Simple`][], i. e. they are ignored.  Underline styles and
+//! colors are not supported either, so underlined text always uses a single underline.  Hyperlinks
+//! are ignored.
+//!
+//! It also implements `TryFrom` for these conversions:
+//! - [`cursive::theme::Style`][] to [`Style`][]
+//! - [`cursive::utils::markup::StyledString`][] to [`StyledText`][]
+//!
+//! The terminal default color and the parent color are converted to `None`.  Palette colors
+//! cannot be resolved without the `cursive` theme, so they are reported in the
+//! [`ConversionError`][].
 //!
 //! # Example
 //!
@@ -28,18 +43,29 @@
 //! [`cursive::theme::BaseColor`]: https://docs.rs/cursive/latest/cursive/theme/enum.BaseColor.html
 //! [`cursive::theme::Color`]: https://docs.rs/cursive/latest/cursive/theme/enum.Color.html
 //! [`cursive::theme::Effect`]: https://docs.rs/cursive/latest/cursive/theme/enum.Effect.html
+//! [`cursive::theme::Effect::Simple`]: https://docs.rs/cursive/latest/cursive/theme/enum.Effect.html#variant.Simple
 //! [`cursive::theme::Style`]: https://docs.rs/cursive/latest/cursive/theme/struct.Style.html
 //! [`cursive::utils::markup::StyledString`]: https://docs.rs/cursive/latest/cursive/utils/markup/type.StyledString.html
 //! [`AnsiColor`]: ../enum.AnsiColor.html
 //! [`Color`]: ../enum.Color.html
+//! [`ConversionError`]: ../struct.ConversionError.html
 //! [`Effect`]: ../enum.Effect.html
+//! [`Dim`]: ../enum.Effect.html#variant.Dim
+//! [`Hidden`]: ../enum.Effect.html#variant.Hidden
+//! [`Overline`]: ../enum.Effect.html#variant.Overline
 //! [`Style`]: ../struct.Style.html
 //! [`StyledStr`]: ../struct.StyledStr.html
 //! [`StyledString`]: ../struct.StyledString.html
+//! [`StyledText`]: ../struct.StyledText.html
+
+use std::cmp;
+use std::convert::TryFrom;
 
 use cursive::{theme, utils::markup};
 
-use crate::{AnsiColor, AnsiMode, Color, Effect, Style, StyledStr, StyledString};
+use crate::{
+    AnsiColor, AnsiMode, Color, ConversionError, Effect, Style, StyledStr, StyledString, StyledText,
+};
 
 impl From<Color> for theme::Color {
     fn from(color: Color) -> theme::Color {
@@ -48,6 +74,7 @@ impl From<Color> for theme::Color {
                 AnsiMode::Dark => theme::Color::Dark(color.into()),
                 AnsiMode::Light => theme::Color::Light(color.into()),
             },
+            Color::Indexed(index) => theme::Color::from_256colors(index),
             Color::Rgb { r, g, b } => theme::Color::Rgb(r, g, b),
         }
     }
@@ -75,6 +102,9 @@ impl From<Effect> for theme::Effect {
             Effect::Italic => theme::Effect::Italic,
             Effect::Underline => theme::Effect::Underline,
             Effect::Strikethrough => theme::Effect::Strikethrough,
+            Effect::Reverse => theme::Effect::Reverse,
+            Effect::Blink => theme::Effect::Blink,
+            Effect::Dim | Effect::Hidden | Effect::Overline => theme::Effect::Simple,
         }
     }
 }
@@ -82,7 +112,12 @@ impl From<Effect> for theme::Effect {
 impl From<Style> for theme::Style {
     fn from(style: Style) -> theme::Style {
         theme::Style {
-            effects: style.effects.into_iter().map(theme::Effect::from).collect(),
+            effects: style
+                .effects
+                .into_iter()
+                .map(theme::Effect::from)
+                .filter(|effect| *effect != theme::Effect::Simple)
+                .collect(),
             color: get_color_style(style.fg, style.bg),
         }
     }
@@ -129,3 +164,109 @@ impl From<StyledString> for markup::StyledString {
         }
     }
 }
+
+impl<'a> From<&'a StyledText> for markup::StyledString {
+    fn from(text: &'a StyledText) -> markup::StyledString {
+        text.iter()
+            .map(StyledStr::from)
+            .map(markup::StyledString::from)
+            .collect()
+    }
+}
+
+impl From<StyledText> for markup::StyledString {
+    fn from(text: StyledText) -> markup::StyledString {
+        text.into_iter().map(markup::StyledString::from).collect()
+    }
+}
+
+impl From<theme::BaseColor> for AnsiColor {
+    fn from(color: theme::BaseColor) -> AnsiColor {
+        match color {
+            theme::BaseColor::Black => AnsiColor::Black,
+            theme::BaseColor::Red => AnsiColor::Red,
+            theme::BaseColor::Green => AnsiColor::Green,
+            theme::BaseColor::Yellow => AnsiColor::Yellow,
+            theme::BaseColor::Blue => AnsiColor::Blue,
+            theme::BaseColor::Magenta => AnsiColor::Magenta,
+            theme::BaseColor::Cyan => AnsiColor::Cyan,
+            theme::BaseColor::White => AnsiColor::White,
+        }
+    }
+}
+
+impl TryFrom<theme::Style> for Style {
+    type Error = ConversionError<Style>;
+
+    fn try_from(style: theme::Style) -> Result<Style, ConversionError<Style>> {
+        let mut unsupported = Vec::new();
+        let mut result = Style {
+            fg: get_color(style.color.front, &mut unsupported),
+            bg: get_color(style.color.back, &mut unsupported),
+            ..Default::default()
+        };
+        for effect in style.effects {
+            let effect = match effect {
+                theme::Effect::Simple => continue,
+                theme::Effect::Reverse => Effect::Reverse,
+                theme::Effect::Bold => Effect::Bold,
+                theme::Effect::Italic => Effect::Italic,
+                theme::Effect::Strikethrough => Effect::Strikethrough,
+                theme::Effect::Underline => Effect::Underline,
+                theme::Effect::Blink => Effect::Blink,
+            };
+            result.set_effect(effect, true);
+        }
+        ConversionError::check(result, unsupported)
+    }
+}
+
+impl<'a> TryFrom<&'a markup::StyledString> for StyledText {
+    type Error = ConversionError<StyledText>;
+
+    fn try_from(s: &'a markup::StyledString) -> Result<StyledText, ConversionError<StyledText>> {
+        let mut unsupported = Vec::new();
+        let mut text = StyledText::new();
+        for span in s.spans() {
+            let style = ConversionError::unwrap_into(Style::try_from(*span.attr), &mut unsupported);
+            let style = if style == Style::default() {
+                None
+            } else {
+                Some(style)
+            };
+            text.push(StyledString::new(span.content.to_owned(), style));
+        }
+        ConversionError::check(text, unsupported)
+    }
+}
+
+impl TryFrom<markup::StyledString> for StyledText {
+    type Error = ConversionError<StyledText>;
+
+    fn try_from(s: markup::StyledString) -> Result<StyledText, ConversionError<StyledText>> {
+        StyledText::try_from(&s)
+    }
+}
+
+/// Converts a `cursive` color type to a color, returning `None` for the default and the parent
+/// color and adding palette colors to the list of unsupported attributes.
+fn get_color(color: theme::ColorType, unsupported: &mut Vec<String>) -> Option<Color> {
+    match color {
+        theme::ColorType::Color(color) => match color {
+            theme::Color::TerminalDefault => None,
+            theme::Color::Dark(color) => Some(AnsiColor::from(color).dark()),
+            theme::Color::Light(color) => Some(AnsiColor::from(color).light()),
+            theme::Color::Rgb(r, g, b) => Some(Color::Rgb { r, g, b }),
+            theme::Color::RgbLowRes(r, g, b) => {
+                // the components should be at most five, but they are not checked by cursive
+                let (r, g, b) = (cmp::min(r, 5), cmp::min(g, 5), cmp::min(b, 5));
+                Some(Color::Indexed(16 + 36 * r + 6 * g + b))
+            }
+        },
+        theme::ColorType::InheritParent => None,
+        theme::ColorType::Palette(color) => {
+            unsupported.push(format!("{:?}", color));
+            None
+        }
+    }
+}
@@ -10,8 +10,19 @@
 //! - [`Style`][] to [`genpdf::style::Style`][]
 //! - [`StyledStr`][] and [`StyledString`][] to [`genpdf::style::StyledStr`][] and
 //!   [`genpdf::style::StyledString`][]
+//! - [`StyledText`][] to [`genpdf::elements::Paragraph`][]
 //!
-//! # Example
+//! `genpdf` only supports the bold and italic text effects and foreground colors.  All other
+//! effects and the background color are ignored by these conversions.  `genpdf` does not support
+//! links either, so hyperlinks are ignored too.
+//!
+//! The [`StyledParagraph`][] element can be used instead of [`genpdf::elements::Paragraph`][] to
+//! also draw the background color as well as the underline and strikethrough effects.
+//!
+//! If the `syntect-parsing` feature is enabled, the [`highlight_document`][] function can be used
+//! to generate a document with syntax-highlighted source code.
+//!
+//! # Examples
 //!
 //! Adding a string to a paragraph:
 //!
@@ -20,7 +31,27 @@
 //! let p = genpdf::elements::Paragraph::new(text);
 //! ```
 //!
+//! Adding highlighted text to a document:
+//!
+//! ```no_run
+//! use text_style::{genpdf::StyledParagraph, AnsiColor, StyledStr};
+//!
+//! let font_family = genpdf::fonts::from_files("./fonts", "LiberationSans", None)
+//!     .expect("Failed to load font family");
+//! let mut doc = genpdf::Document::new(font_family);
+//! let text = vec![
+//!     StyledStr::plain("warning:").on(AnsiColor::Yellow.light()).bold(),
+//!     StyledStr::plain(" this is ").underline(),
+//!     StyledStr::plain("deprecated").strikethrough(),
+//! ];
+//! doc.push(text.iter().collect::<StyledParagraph>());
+//! doc.render_to_file("output.pdf").expect("Failed to render document");
+//! ```
+//!
+//! [`StyledParagraph`]: struct.StyledParagraph.html
+//! [`highlight_document`]: fn.highlight_document.html
 //! [`genpdf`]: https://docs.rs/genpdf
+//! [`genpdf::elements::Paragraph`]: https://docs.rs/genpdf/latest/genpdf/elements/struct.Paragraph.html
 //! [`genpdf::style::Color`]: https://docs.rs/genpdf/latest/genpdf/style/enum.Color.html
 //! [`genpdf::style::Style`]: https://docs.rs/genpdf/latest/genpdf/style/struct.Style.html
 //! [`genpdf::style::StyledStr`]: https://docs.rs/genpdf/latest/genpdf/style/struct.StyledStr.html
@@ -29,43 +60,28 @@
 //! [`Style`]: ../struct.Style.html
 //! [`StyledStr`]: ../struct.StyledStr.html
 //! [`StyledString`]: ../struct.StyledString.html
+//! [`StyledText`]: ../struct.StyledText.html
+
+use std::collections;
+use std::iter;
+use std::mem;
+
+use genpdf::{elements, error, render, style, Mm, Position, Size};
+#[cfg(feature = "syntect-parsing")]
+use syntect::{easy, highlighting, parsing, util};
 
-use genpdf::style;
+use unicode_segmentation::UnicodeSegmentation as _;
 
-use crate::{AnsiColor, AnsiMode, Color, Style, StyledStr, StyledString};
+#[cfg(feature = "syntect-parsing")]
+use crate::width;
+use crate::wrap::{self, Token};
+use crate::{palette, Color, Effect, Style, StyledStr, StyledString, StyledText};
 
 impl From<Color> for style::Color {
     fn from(c: Color) -> style::Color {
-        match c {
-            Color::Ansi { color, mode } => get_rgb_color(color, mode),
-            Color::Rgb { r, g, b } => style::Color::Rgb(r, g, b),
-        }
-    }
-}
-
-fn get_rgb_color(color: AnsiColor, mode: AnsiMode) -> style::Color {
-    use AnsiColor::*;
-    use AnsiMode::*;
-
-    let (r, g, b) = match (mode, color) {
-        (Dark, Black) => (0, 0, 0),
-        (Dark, Red) => (170, 0, 0),
-        (Dark, Green) => (0, 170, 0),
-        (Dark, Yellow) => (170, 85, 0),
-        (Dark, Blue) => (0, 0, 170),
-        (Dark, Magenta) => (170, 0, 170),
-        (Dark, Cyan) => (0, 170, 170),
-        (Dark, White) => (170, 170, 170),
-        (Light, Black) => (85, 85, 85),
-        (Light, Red) => (255, 85, 85),
-        (Light, Green) => (85, 255, 85),
-        (Light, Yellow) => (255, 255, 85),
-        (Light, Blue) => (85, 85, 255),
-        (Light, Magenta) => (255, 85, 255),
-        (Light, Cyan) => (85, 255, 255),
-        (Light, White) => (255, 255, 255),
-    };
-    style::Color::Rgb(r, g, b)
+        let (r, g, b) = palette::rgb(c);
+        style::Color::Rgb(r, g, b)
+    }
 }
 
 impl From<Style> for style::Style {
@@ -125,3 +141,700 @@ impl From<StyledString> for style::StyledString {
         style::StyledString::new(s.s, s.style.map(style::Style::from).unwrap_or_default())
     }
 }
+
+impl<'a> From<&'a StyledText> for elements::Paragraph {
+    fn from(text: &'a StyledText) -> elements::Paragraph {
+        text.iter().map(style::StyledString::from).collect()
+    }
+}
+
+impl From<StyledText> for elements::Paragraph {
+    fn from(text: StyledText) -> elements::Paragraph {
+        text.into_iter().map(style::StyledString::from).collect()
+    }
+}
+
+/// A paragraph of styled text that draws background colors and text decorations.
+///
+/// In contrast to [`genpdf::elements::Paragraph`][], this element draws the background color of
+/// the styled strings as well as the underline and strikethrough effects.  The lines are drawn
+/// with the underline color, if set, or the foreground color of the string.
+///
+/// `genpdf` cannot fill areas, so each background is drawn as a path that zigzags across the area
+/// in steps of 0.3 mm.  The background of a string on a line that is 5 mm high consists of about
+/// 30 path segments.  If many strings have a background color, this increases the file size and
+/// the rendering time of the document noticeably.
+///
+/// The text is wrapped using the same rules as the [`wrap`][] module, but the width of the text is
+/// measured using the font metrics:  The text is wrapped at whitespace except non-breaking spaces.
+/// Words that are wider than the available space are broken at grapheme cluster boundaries.
+/// Newlines start a new line, and whitespace at the start of these lines is preserved.
+///
+/// # Example
+///
+/// ```
+/// use text_style::{genpdf::StyledParagraph, AnsiColor, StyledStr};
+///
+/// let p = StyledParagraph::new(StyledStr::plain("fn").with(AnsiColor::Blue.dark()))
+///     .string(StyledStr::plain(" main() {}"))
+///     .aligned(genpdf::Alignment::Center);
+/// ```
+///
+/// [`genpdf::elements::Paragraph`]: https://docs.rs/genpdf/latest/genpdf/elements/struct.Paragraph.html
+/// [`wrap`]: ../wrap/index.html
+#[derive(Clone, Debug, Default)]
+pub struct StyledParagraph {
+    text: Vec<StyledString>,
+    alignment: genpdf::Alignment,
+    lines: Option<collections::VecDeque<Vec<Segment>>>,
+}
+
+impl StyledParagraph {
+    /// Creates a new paragraph with the given content.
+    pub fn new<'a>(s: impl Into<StyledStr<'a>>) -> StyledParagraph {
+        StyledParagraph::default().string(s)
+    }
+
+    /// Adds a string to the end of this paragraph.
+    pub fn push<'a>(&mut self, s: impl Into<StyledStr<'a>>) {
+        self.text.push(s.into().into());
+    }
+
+    /// Adds a string to the end of this paragraph and returns the paragraph.
+    pub fn string<'a>(mut self, s: impl Into<StyledStr<'a>>) -> Self {
+        self.push(s);
+        self
+    }
+
+    /// Sets the alignment of this paragraph.
+    pub fn set_alignment(&mut self, alignment: genpdf::Alignment) {
+        self.alignment = alignment;
+    }
+
+    /// Sets the alignment of this paragraph and returns the paragraph.
+    pub fn aligned(mut self, alignment: genpdf::Alignment) -> Self {
+        self.set_alignment(alignment);
+        self
+    }
+
+    fn get_offset(&self, width: Mm, max_width: Mm) -> Mm {
+        match self.alignment {
+            genpdf::Alignment::Left => Mm::default(),
+            genpdf::Alignment::Center => (max_width - width) / 2.0,
+            genpdf::Alignment::Right => max_width - width,
+        }
+    }
+}
+
+impl genpdf::Element for StyledParagraph {
+    fn render(
+        &mut self,
+        context: &genpdf::Context,
+        mut area: render::Area<'_>,
+        style: style::Style,
+    ) -> Result<genpdf::RenderResult, error::Error> {
+        let mut result = genpdf::RenderResult::default();
+        let mut lines = match self.lines.take() {
+            Some(lines) => lines,
+            None => layout(&self.text, &context.font_cache, area.size().width, style),
+        };
+        let line_height = style.line_height(&context.font_cache);
+        let glyph_height = style
+            .font(&context.font_cache)
+            .glyph_height(style.font_size());
+
+        while let Some(line) = lines.pop_front() {
+            if line_height > area.size().height {
+                lines.push_front(line);
+                result.has_more = true;
+                break;
+            }
+
+            let width: Mm = line.iter().map(|segment| segment.width).sum();
+            let start = self.get_offset(width, area.size().width);
+            let mut x = start;
+
+            for segment in &line {
+                if let Some(bg) = segment.style.bg {
+                    fill_rect(&area, x, segment.width, line_height, bg.into());
+                }
+                x += segment.width;
+            }
+
+            if !line.is_empty() {
+                let position = Position::new(start, 0);
+                if let Some(mut section) = area.text_section(&context.font_cache, position, style) {
+                    for segment in &line {
+                        section.print_str(&segment.s, segment.pdf_style)?;
+                    }
+                } else {
+                    lines.push_front(line);
+                    result.has_more = true;
+                    break;
+                }
+            }
+
+            // genpdf places the baseline at the bottom of the glyph box
+            let mut x = start;
+            for segment in &line {
+                let color = segment
+                    .style
+                    .underline_color
+                    .or(segment.style.fg)
+                    .map(style::Color::from);
+                let line_style = color.map(style::Style::from).unwrap_or_default();
+                let effects = segment.style.effects;
+                if effects.is_set(Effect::Underline) {
+                    let y = glyph_height * 1.05;
+                    draw_hline(&area, x, segment.width, y, line_style);
+                }
+                if effects.is_set(Effect::Strikethrough) {
+                    let y = glyph_height * 0.55;
+                    draw_hline(&area, x, segment.width, y, line_style);
+                }
+                x += segment.width;
+            }
+
+            result.size = result.size.stack_vertical(Size::new(width, line_height));
+            area.add_offset(Position::new(0, line_height));
+        }
+
+        self.lines = Some(lines);
+        Ok(result)
+    }
+}
+
+impl From<StyledText> for StyledParagraph {
+    fn from(text: StyledText) -> StyledParagraph {
+        StyledParagraph {
+            text: text.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl<'a> From<&'a StyledText> for StyledParagraph {
+    fn from(text: &'a StyledText) -> StyledParagraph {
+        text.iter().collect()
+    }
+}
+
+impl<'a, S: Into<StyledStr<'a>>> iter::Extend<S> for StyledParagraph {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        for s in iter {
+            self.push(s);
+        }
+    }
+}
+
+impl<'a, S: Into<StyledStr<'a>>> iter::FromIterator<S> for StyledParagraph {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> StyledParagraph {
+        let mut paragraph = StyledParagraph::default();
+        paragraph.extend(iter);
+        paragraph
+    }
+}
+
+/// Options for the [`highlight_document`][] function.
+///
+/// *Requires the `syntect-parsing` feature.*
+///
+/// [`highlight_document`]: fn.highlight_document.html
+#[cfg(feature = "syntect-parsing")]
+#[derive(Clone, Debug, PartialEq)]
+pub struct CodeOptions {
+    /// The file name that is used as the document title and printed in the page headers.
+    pub title: Option<String>,
+    /// Whether the page headers are printed.
+    pub headers: bool,
+    /// Whether line numbers are printed.
+    pub line_numbers: bool,
+    /// The font size in points.
+    pub font_size: u8,
+    /// The distance between the tab stops that tab characters are expanded to.  If it is zero, tab
+    /// characters are removed.
+    pub tab_width: usize,
+    /// Whether the pages are filled with the background color of the theme.
+    ///
+    /// `genpdf` cannot fill areas, so the background is drawn as a path with about 1000 segments
+    /// per A4 page, see [`StyledParagraph`][].  This increases the file size and the rendering time
+    /// of the document considerably.
+    ///
+    /// [`StyledParagraph`]: struct.StyledParagraph.html
+    pub background: bool,
+    /// The paper size of the document.
+    pub paper_size: Size,
+    /// The page margins.
+    pub margins: genpdf::Margins,
+}
+
+#[cfg(feature = "syntect-parsing")]
+impl CodeOptions {
+    /// Creates new options with the default values:  A4 paper with margins of 10 mm, a 10 pt font,
+    /// a tab width of four spaces, the page background, line numbers and page headers without a
+    /// title.
+    pub fn new() -> CodeOptions {
+        Default::default()
+    }
+
+    /// Sets the file name that is used as the document title and printed in the page headers.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets whether the page headers are printed.
+    pub fn headers(mut self, headers: bool) -> Self {
+        self.headers = headers;
+        self
+    }
+
+    /// Sets whether line numbers are printed.
+    pub fn line_numbers(mut self, line_numbers: bool) -> Self {
+        self.line_numbers = line_numbers;
+        self
+    }
+
+    /// Sets the font size in points.
+    pub fn font_size(mut self, font_size: u8) -> Self {
+        self.font_size = font_size;
+        self
+    }
+
+    /// Sets the distance between the tab stops that tab characters are expanded to.
+    pub fn tab_width(mut self, tab_width: usize) -> Self {
+        self.tab_width = tab_width;
+        self
+    }
+
+    /// Sets whether the pages are filled with the background color of the theme.
+    pub fn background(mut self, background: bool) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// Sets the paper size of the document.
+    pub fn paper_size(mut self, paper_size: impl Into<Size>) -> Self {
+        self.paper_size = paper_size.into();
+        self
+    }
+
+    /// Sets the page margins.
+    pub fn margins(mut self, margins: impl Into<genpdf::Margins>) -> Self {
+        self.margins = margins.into();
+        self
+    }
+}
+
+#[cfg(feature = "syntect-parsing")]
+impl Default for CodeOptions {
+    fn default() -> CodeOptions {
+        CodeOptions {
+            title: None,
+            headers: true,
+            line_numbers: true,
+            font_size: 10,
+            tab_width: 4,
+            background: true,
+            paper_size: genpdf::PaperSize::A4.into(),
+            margins: genpdf::Margins::all(10),
+        }
+    }
+}
+
+/// Generates a document with the given source code, highlighted with the given syntax and theme.
+///
+/// *Requires the `syntect-parsing` feature.*
+///
+/// The given font family is used for the code, the line numbers and the page headers, so it
+/// should be a monospace font.  The pages are filled with the background color of the theme unless
+/// [`CodeOptions::background`][] is disabled, and the line numbers are printed with the gutter
+/// color of the theme, if set.  Lines that are wider than the page are wrapped, and the
+/// continuation lines are aligned with the code.  Tabs are expanded to the tab stops set by
+/// [`CodeOptions::tab_width`][].
+///
+/// The returned document can be customized further before rendering it.
+///
+/// [`CodeOptions::background`]: struct.CodeOptions.html#structfield.background
+/// [`CodeOptions::tab_width`]: struct.CodeOptions.html#structfield.tab_width
+///
+/// # Example
+///
+/// ```no_run
+/// use syntect::{highlighting, parsing};
+/// use text_style::genpdf::{self, CodeOptions};
+///
+/// let ps = parsing::SyntaxSet::load_defaults_newlines();
+/// let ts = highlighting::ThemeSet::load_defaults();
+/// let syntax = ps.find_syntax_by_extension("rs").unwrap();
+///
+/// let font_family = ::genpdf::fonts::from_files("./fonts", "DejaVuSansMono", None)
+///     .expect("Failed to load font family");
+/// let code = std::fs::read_to_string("src/main.rs").expect("Failed to read file");
+/// let options = CodeOptions::new().title("src/main.rs");
+/// let doc = genpdf::highlight_document(
+///     font_family,
+///     &code,
+///     &ps,
+///     syntax,
+///     &ts.themes["InspiredGitHub"],
+///     &options,
+/// );
+/// doc.render_to_file("main.pdf").expect("Failed to render document");
+/// ```
+#[cfg(feature = "syntect-parsing")]
+pub fn highlight_document(
+    font_family: genpdf::fonts::FontFamily<genpdf::fonts::FontData>,
+    s: &str,
+    syntax_set: &parsing::SyntaxSet,
+    syntax: &parsing::SyntaxReference,
+    theme: &highlighting::Theme,
+    options: &CodeOptions,
+) -> genpdf::Document {
+    let fg = theme.settings.foreground.map(Color::from);
+    let bg = theme.settings.background.map(Color::from);
+    let gutter_fg = theme.settings.gutter_foreground.map(Color::from).or(fg);
+
+    let mut doc = genpdf::Document::new(font_family);
+    if let Some(title) = &options.title {
+        doc.set_title(title.clone());
+    }
+    doc.set_font_size(options.font_size);
+    doc.set_paper_size(options.paper_size);
+    doc.set_page_decorator(CodeDecorator {
+        page: 0,
+        title: options.title.clone().filter(|_| options.headers),
+        margins: options.margins,
+        background: bg.filter(|_| options.background).map(style::Color::from),
+        color: fg.map(style::Color::from),
+    });
+
+    let line_count = util::LinesWithEndings::from(s).count();
+    let number_width = line_count.to_string().len();
+    let mut h = easy::HighlightLines::new(syntax, theme);
+    for (i, line) in util::LinesWithEndings::from(s).enumerate() {
+        let mut paragraph = StyledParagraph::default();
+        let mut column = 0;
+        for (style, s) in h.highlight(line, syntax_set) {
+            let s = s.trim_end_matches(&['\r', '\n'][..]);
+            let s = width::expand_tabs(s, options.tab_width, column);
+            column += width::str_width(&s);
+            let mut style = Style::from(style);
+            if style.bg == bg {
+                style.bg = None;
+            }
+            paragraph.push(StyledStr::styled(&s, style));
+        }
+        let number = if options.line_numbers {
+            Some(format!("{:>1$}  ", i + 1, number_width))
+        } else {
+            None
+        };
+        doc.push(CodeLine {
+            number,
+            number_style: gutter_fg
+                .map(|color| style::Style::from(style::Color::from(color)))
+                .unwrap_or_default(),
+            paragraph,
+            is_started: false,
+        });
+    }
+    doc
+}
+
+/// A line of source code with an optional line number, used by [`highlight_document`][].
+///
+/// [`highlight_document`]: fn.highlight_document.html
+#[cfg(feature = "syntect-parsing")]
+struct CodeLine {
+    number: Option<String>,
+    number_style: style::Style,
+    paragraph: StyledParagraph,
+    is_started: bool,
+}
+
+#[cfg(feature = "syntect-parsing")]
+impl genpdf::Element for CodeLine {
+    fn render(
+        &mut self,
+        context: &genpdf::Context,
+        mut area: render::Area<'_>,
+        style: style::Style,
+    ) -> Result<genpdf::RenderResult, error::Error> {
+        let mut result = genpdf::RenderResult::default();
+        let line_height = style.line_height(&context.font_cache);
+        if line_height > area.size().height {
+            result.has_more = true;
+            return Ok(result);
+        }
+
+        let mut number_width = Mm::default();
+        if let Some(number) = &self.number {
+            let number_style = style.and(self.number_style);
+            number_width = number_style.str_width(&context.font_cache, number);
+            if !self.is_started {
+                area.print_str(
+                    &context.font_cache,
+                    Position::default(),
+                    number_style,
+                    number,
+                )?;
+            }
+            area.add_offset(Position::new(number_width, 0));
+        }
+
+        result = self.paragraph.render(context, area, style)?;
+        if !self.is_started && result.size.height == Mm::default() {
+            // empty lines still occupy one line
+            result.size.height = line_height;
+        }
+        result.size.width += number_width;
+        self.is_started = true;
+        Ok(result)
+    }
+}
+
+/// The page decorator used by [`highlight_document`][].
+///
+/// [`highlight_document`]: fn.highlight_document.html
+#[cfg(feature = "syntect-parsing")]
+struct CodeDecorator {
+    page: usize,
+    title: Option<String>,
+    margins: genpdf::Margins,
+    background: Option<style::Color>,
+    color: Option<style::Color>,
+}
+
+#[cfg(feature = "syntect-parsing")]
+impl genpdf::PageDecorator for CodeDecorator {
+    fn decorate_page<'a>(
+        &mut self,
+        context: &genpdf::Context,
+        mut area: render::Area<'a>,
+        style: style::Style,
+    ) -> Result<render::Area<'a>, error::Error> {
+        self.page += 1;
+        if let Some(background) = self.background {
+            let size = area.size();
+            fill_rect(&area, Mm::default(), size.width, size.height, background);
+        }
+        area.add_margins(self.margins);
+
+        if let Some(title) = &self.title {
+            let style = match self.color {
+                Some(color) => style.and(color),
+                None => style,
+            };
+            let page = format!("{}", self.page);
+            let page_width = style.str_width(&context.font_cache, &page);
+            area.print_str(&context.font_cache, Position::default(), style, title)?;
+            let position = Position::new(area.size().width - page_width, 0);
+            area.print_str(&context.font_cache, position, style, page)?;
+            area.add_offset(Position::new(
+                0,
+                style.line_height(&context.font_cache) * 2.0,
+            ));
+        }
+        Ok(area)
+    }
+}
+
+/// A part of a styled string that is placed on a single line.
+#[derive(Clone, Debug)]
+struct Segment {
+    s: String,
+    style: Style,
+    pdf_style: style::Style,
+    width: Mm,
+}
+
+/// The state of the line wrapping algorithm of the [`StyledParagraph`][] element.
+///
+/// This is the same algorithm as in the [`wrap`][] module, but it measures the width of the text
+/// using the font metrics.
+///
+/// [`StyledParagraph`]: struct.StyledParagraph.html
+/// [`wrap`]: ../wrap/index.html
+struct Layout<'f> {
+    font_cache: &'f genpdf::fonts::FontCache,
+    max_width: Mm,
+    lines: collections::VecDeque<Vec<Segment>>,
+    line: Vec<Segment>,
+    line_width: Mm,
+    has_content: bool,
+    is_paragraph_start: bool,
+    space: Vec<Segment>,
+}
+
+impl<'f> Layout<'f> {
+    fn segment(&self, s: &str, style: Style, pdf_style: style::Style) -> Segment {
+        Segment {
+            s: s.to_owned(),
+            style,
+            pdf_style,
+            width: pdf_style.str_width(self.font_cache, s),
+        }
+    }
+
+    fn push(&mut self, segment: Segment) {
+        self.line_width += segment.width;
+        self.line.push(segment);
+    }
+
+    fn finish_line(&mut self) {
+        let line = mem::take(&mut self.line);
+        self.lines.push_back(line);
+        self.line_width = Mm::default();
+        self.has_content = false;
+        self.space.clear();
+    }
+
+    fn segments(&self, fragments: Vec<StyledStr<'_>>, base_style: style::Style) -> Vec<Segment> {
+        fragments
+            .into_iter()
+            .map(|fragment| {
+                let style = fragment.style.unwrap_or_default();
+                let pdf_style = base_style.and(style::Style::from(style));
+                self.segment(fragment.s, style, pdf_style)
+            })
+            .collect()
+    }
+
+    fn handle(&mut self, token: Token<'_>, base_style: style::Style) {
+        match token {
+            Token::Newline => {
+                self.finish_line();
+                self.is_paragraph_start = true;
+            }
+            Token::Space(fragments) => {
+                let segments = self.segments(fragments, base_style);
+                if self.has_content {
+                    self.space = segments;
+                } else if self.is_paragraph_start {
+                    for segment in segments {
+                        self.push(segment);
+                    }
+                }
+            }
+            Token::Word(fragments) => {
+                let segments = self.segments(fragments, base_style);
+                self.push_word(segments);
+                self.is_paragraph_start = false;
+            }
+        }
+    }
+
+    fn push_word(&mut self, word: Vec<Segment>) {
+        let word_width: Mm = word.iter().map(|segment| segment.width).sum();
+        let space = mem::take(&mut self.space);
+        if self.has_content {
+            let space_width: Mm = space.iter().map(|segment| segment.width).sum();
+            if self.line_width + space_width + word_width > self.max_width {
+                self.finish_line();
+            } else {
+                for segment in space {
+                    self.push(segment);
+                }
+            }
+        }
+
+        if self.line_width + word_width <= self.max_width {
+            for segment in word {
+                self.push(segment);
+            }
+            self.has_content = true;
+            return;
+        }
+
+        for segment in word {
+            let mut rest = segment.s.as_str();
+            while !rest.is_empty() {
+                let available = self.max_width - self.line_width;
+                let mut len = 0;
+                for (idx, grapheme) in rest.grapheme_indices(true) {
+                    let end = idx + grapheme.len();
+                    if segment.pdf_style.str_width(self.font_cache, &rest[..end]) > available {
+                        break;
+                    }
+                    len = end;
+                }
+                if len == 0 {
+                    if self.has_content {
+                        self.finish_line();
+                        continue;
+                    }
+                    // not even a single grapheme cluster fits into the empty line
+                    len = rest.graphemes(true).next().map_or(rest.len(), str::len);
+                }
+                let part = self.segment(&rest[..len], segment.style, segment.pdf_style);
+                self.push(part);
+                self.has_content = true;
+                rest = &rest[len..];
+            }
+        }
+    }
+}
+
+/// Splits the given text into lines that are not wider than `max_width`.
+fn layout(
+    text: &[StyledString],
+    font_cache: &genpdf::fonts::FontCache,
+    max_width: Mm,
+    base_style: style::Style,
+) -> collections::VecDeque<Vec<Segment>> {
+    let mut layout = Layout {
+        font_cache,
+        max_width,
+        lines: collections::VecDeque::new(),
+        line: Vec::new(),
+        line_width: Mm::default(),
+        has_content: false,
+        is_paragraph_start: true,
+        space: Vec::new(),
+    };
+    for token in wrap::tokenize(text.iter().map(StyledStr::from)) {
+        layout.handle(token, base_style);
+    }
+    if layout.has_content {
+        layout.finish_line();
+    }
+    layout.lines
+}
+
+/// Draws a horizontal line with the given style.
+fn draw_hline(area: &render::Area<'_>, x: Mm, width: Mm, y: Mm, style: style::Style) {
+    area.draw_line(
+        vec![Position::new(x, y), Position::new(x + width, y)],
+        style,
+    );
+}
+
+/// Fills a rectangle at the top of the given area with the given color.
+///
+/// `genpdf` can neither fill shapes nor change the line width, so the rectangle is filled with a
+/// single path that zigzags between its left and right border.  The path is drawn with the default
+/// line width of one point.
+fn fill_rect(area: &render::Area<'_>, x: Mm, width: Mm, height: Mm, color: style::Color) {
+    // the distance between the horizontal segments is slightly smaller than the line width, and
+    // the vertical segments are inset by half the line width so that they don't exceed the
+    // rectangle
+    let step = Mm::from(0.3);
+    let inset = Mm::from(25.4 / 72.0 / 2.0);
+    let (left, right) = (x + inset, x + width - inset);
+    let mut points = Vec::new();
+    let mut y = step / 2.0;
+    while y + step / 2.0 <= height {
+        if points.len() % 4 == 0 {
+            points.push(Position::new(left, y));
+            points.push(Position::new(right, y));
+        } else {
+            points.push(Position::new(right, y));
+            points.push(Position::new(left, y));
+        }
+        y += step;
+    }
+    if !points.is_empty() {
+        area.draw_line(points, style::Style::from(color));
+    }
+}
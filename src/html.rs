@@ -0,0 +1,827 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+//! Parser and renderer for HTML.
+//!
+//! # Parsing
+//!
+//! The [`parse`][] function converts a simple HTML fragment, for example a chat message, into a
+//! [`StyledText`][].  The parser is lenient:  it never fails, unknown elements are dropped while
+//! their text is kept, and unclosed elements are closed implicitly.  Whitespace is preserved,
+//! `<br>` is converted to a newline and character references like `&amp;` are decoded.  These
+//! elements are supported:
+//!
+//! - `<b>`, `<strong>`: bold
+//! - `<i>`, `<em>`: italic
+//! - `<u>`, `<ins>`: underline
+//! - `<s>`, `<strike>`, `<del>`: strikethrough
+//! - `<code>`: the semantic style `code`, see [`theme`][]
+//! - `<a href="…">`: hyperlink
+//! - `<font color="…">`: foreground color
+//!
+//! The `style` attribute of all elements is evaluated too.  The supported CSS properties are
+//! `color`, `background-color`, `background`, `font-weight`, `font-style`, `text-decoration`,
+//! `text-decoration-line` and `visibility`.  CSS colors (named colors, `#rgb`, `#rrggbb`,
+//! `rgb(…)` and `rgba(…)`) are converted to [`Color::Rgb`][].
+//!
+//! # Rendering
+//!
+//! The [`render`][] and [`render_iter`][] functions write styled strings as HTML.  The text is
+//! escaped, and each styled string is wrapped in a `<span>` element that sets the colors and
+//! effects of the string.  Strings without a style are not wrapped.  Hyperlinks are rendered as
+//! `<a>` elements if they are relative or use the `http`, `https` or `mailto` scheme.  Other
+//! hyperlinks, for example `javascript:` URLs, are dropped.
+//!
+//! The [`Options`][] struct controls how the styles are written:
+//!
+//! - By default, the styles are written as inline `style` attributes.
+//! - If a class prefix is set with [`Options::classes`][], the ANSI colors and the text effects
+//!   are written as CSS class names, and [`Options::stylesheet`][] generates the matching
+//!   stylesheet.  Indexed and RGB colors and underline colors are still written as inline styles.
+//!
+//! ANSI colors are converted to RGB values using a [`Palette`][], per default the VGA palette.
+//! The reverse effect swaps the foreground and the background color, and the blink effect is
+//! ignored.
+//!
+//! # Examples
+//!
+//! Parsing HTML:
+//!
+//! ```
+//! use text_style::{html, Color, StyledString};
+//!
+//! let text = html::parse("<b>Warning:</b> <span style=\"color: red\">x &lt; 1</span>");
+//! assert_eq!(text.spans(), &[
+//!     StyledString::plain("Warning:".to_owned()).bold(),
+//!     StyledString::plain(" ".to_owned()),
+//!     StyledString::plain("x < 1".to_owned()).with(Color::Rgb { r: 255, g: 0, b: 0 }),
+//! ]);
+//! ```
+//!
+//! Rendering HTML:
+//!
+//! ```
+//! use text_style::{html, AnsiColor, StyledStr};
+//!
+//! let s = StyledStr::plain("a < b").with(AnsiColor::Red.dark()).bold();
+//!
+//! let mut w = Vec::new();
+//! html::render(&mut w, &s).expect("Failed to render string");
+//! assert_eq!(
+//!     String::from_utf8(w).unwrap(),
+//!     "<span style=\"color:#aa0000;font-weight:bold\">a &lt; b</span>",
+//! );
+//!
+//! let options = html::Options::new().classes("ts-");
+//! let mut w = Vec::new();
+//! html::render_with(&mut w, &s, &options).expect("Failed to render string");
+//! assert_eq!(
+//!     String::from_utf8(w).unwrap(),
+//!     "<span class=\"ts-fg-red ts-bold\">a &lt; b</span>",
+//! );
+//! assert!(options.stylesheet().contains(".ts-fg-red { color: #aa0000; }"));
+//! ```
+//!
+//! [`Color::Rgb`]: ../enum.Color.html#variant.Rgb
+//! [`Options`]: struct.Options.html
+//! [`Options::classes`]: struct.Options.html#method.classes
+//! [`Options::stylesheet`]: struct.Options.html#method.stylesheet
+//! [`Palette`]: ../struct.Palette.html
+//! [`StyledText`]: ../struct.StyledText.html
+//! [`parse`]: fn.parse.html
+//! [`render`]: fn.render.html
+//! [`render_iter`]: fn.render_iter.html
+//! [`theme`]: ../theme/index.html
+
+use std::io;
+use std::iter;
+
+use crate::{
+    spec, AnsiMode, Color, Effect, Hyperlink, Palette, Style, StyledStr, StyledString, StyledText,
+    UnderlineStyle, ANSI_COLORS,
+};
+
+const DECORATIONS: &[(Effect, &str)] = &[
+    (Effect::Underline, "underline"),
+    (Effect::Strikethrough, "line-through"),
+    (Effect::Overline, "overline"),
+];
+
+const UNDERLINE_STYLES: &[UnderlineStyle] = &[
+    UnderlineStyle::Double,
+    UnderlineStyle::Curly,
+    UnderlineStyle::Dotted,
+    UnderlineStyle::Dashed,
+];
+
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+const CSS_COLORS: &[(&str, (u8, u8, u8))] = &[
+    ("aqua", (0, 255, 255)),
+    ("black", (0, 0, 0)),
+    ("blue", (0, 0, 255)),
+    ("cyan", (0, 255, 255)),
+    ("fuchsia", (255, 0, 255)),
+    ("gray", (128, 128, 128)),
+    ("green", (0, 128, 0)),
+    ("grey", (128, 128, 128)),
+    ("lime", (0, 255, 0)),
+    ("magenta", (255, 0, 255)),
+    ("maroon", (128, 0, 0)),
+    ("navy", (0, 0, 128)),
+    ("olive", (128, 128, 0)),
+    ("orange", (255, 165, 0)),
+    ("purple", (128, 0, 128)),
+    ("red", (255, 0, 0)),
+    ("silver", (192, 192, 192)),
+    ("teal", (0, 128, 128)),
+    ("white", (255, 255, 255)),
+    ("yellow", (255, 255, 0)),
+];
+
+/// Options for the HTML renderer.
+///
+/// See the [module documentation](index.html) for more information.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Options {
+    /// The palette that is used to convert ANSI colors to RGB values.
+    pub palette: Palette,
+    /// The prefix for the CSS class names, or `None` if inline styles should be used.
+    pub class_prefix: Option<String>,
+}
+
+impl Options {
+    /// Creates new options that use inline styles and the VGA palette.
+    pub fn new() -> Options {
+        Default::default()
+    }
+
+    /// Sets the palette that is used to convert ANSI colors to RGB values.
+    pub fn palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    /// Uses CSS class names with the given prefix instead of inline styles.
+    pub fn classes(mut self, prefix: impl Into<String>) -> Self {
+        self.class_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Returns the CSS stylesheet with the class names that are used by the renderer, or an empty
+    /// string if the renderer uses inline styles.
+    pub fn stylesheet(&self) -> String {
+        let prefix = match &self.class_prefix {
+            Some(prefix) => prefix,
+            None => return String::new(),
+        };
+
+        let mut rules = Vec::new();
+        for (name, property) in &[("fg", "color"), ("bg", "background-color")] {
+            for mode in &[AnsiMode::Dark, AnsiMode::Light] {
+                for color in ANSI_COLORS {
+                    let color = Color::Ansi {
+                        color: *color,
+                        mode: *mode,
+                    };
+                    rules.push(format!(
+                        ".{}{}-{} {{ {}: {}; }}",
+                        prefix,
+                        name,
+                        color,
+                        property,
+                        self.palette.hex(color)
+                    ));
+                }
+            }
+        }
+        for effect in crate::EFFECTS {
+            if let Some((property, value)) = get_effect_declaration(*effect) {
+                rules.push(format!(
+                    ".{}{} {{ {}: {}; }}",
+                    prefix,
+                    spec::get_effect_name(*effect),
+                    property,
+                    value
+                ));
+            }
+        }
+        // multiple text decorations have to be set in the same declaration, so we add a rule for
+        // every combination of at least two decorations (bit masks 0b011 to 0b111)
+        for i in 3..8 {
+            let decorations: Vec<_> = DECORATIONS
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| i & (1 << j) != 0)
+                .map(|(_, decoration)| decoration)
+                .collect();
+            if decorations.len() > 1 {
+                let selector: Vec<_> = decorations
+                    .iter()
+                    .map(|(effect, _)| format!(".{}{}", prefix, spec::get_effect_name(*effect)))
+                    .collect();
+                let values: Vec<_> = decorations.iter().map(|(_, value)| *value).collect();
+                rules.push(format!(
+                    "{} {{ text-decoration-line: {}; }}",
+                    selector.join(""),
+                    values.join(" ")
+                ));
+            }
+        }
+        for style in UNDERLINE_STYLES {
+            if let Some(name) = spec::get_underline_style_name(*style) {
+                rules.push(format!(
+                    ".{}{} {{ text-decoration-style: {}; }}",
+                    prefix,
+                    name,
+                    get_underline_style_value(*style)
+                ));
+            }
+        }
+
+        let mut stylesheet = rules.join("\n");
+        stylesheet.push('\n');
+        stylesheet
+    }
+
+    /// Returns the class names and the CSS declarations for the given style.
+    fn get_attributes(&self, style: Style) -> (Vec<String>, Vec<String>) {
+        let mut classes = Vec::new();
+        let mut declarations = Vec::new();
+
+        let (fg, bg) = if style.effects.is_set(Effect::Reverse) {
+            (style.bg, style.fg)
+        } else {
+            (style.fg, style.bg)
+        };
+        for (color, name, property) in &[(fg, "fg", "color"), (bg, "bg", "background-color")] {
+            match (color, &self.class_prefix) {
+                (Some(color @ Color::Ansi { .. }), Some(prefix)) => {
+                    classes.push(format!("{}{}-{}", prefix, name, color));
+                }
+                (Some(color), _) => {
+                    declarations.push(format!("{}:{}", property, self.palette.hex(*color)));
+                }
+                (None, _) => {}
+            }
+        }
+
+        let underline_style = style.get_underline_style();
+        match &self.class_prefix {
+            Some(prefix) => {
+                for effect in style.effects {
+                    if get_effect_declaration(effect).is_some() {
+                        classes.push(format!("{}{}", prefix, spec::get_effect_name(effect)));
+                    }
+                }
+                if let Some(name) = underline_style.and_then(spec::get_underline_style_name) {
+                    classes.push(format!("{}{}", prefix, name));
+                }
+            }
+            None => {
+                for effect in style.effects {
+                    match get_effect_declaration(effect) {
+                        Some(("text-decoration-line", _)) | None => {}
+                        Some((property, value)) => {
+                            declarations.push(format!("{}:{}", property, value))
+                        }
+                    }
+                }
+                let decorations: Vec<_> = DECORATIONS
+                    .iter()
+                    .filter(|(effect, _)| style.effects.is_set(*effect))
+                    .map(|(_, value)| *value)
+                    .collect();
+                if !decorations.is_empty() {
+                    declarations.push(format!("text-decoration-line:{}", decorations.join(" ")));
+                }
+                match underline_style {
+                    Some(UnderlineStyle::Single) | None => {}
+                    Some(underline_style) => declarations.push(format!(
+                        "text-decoration-style:{}",
+                        get_underline_style_value(underline_style)
+                    )),
+                }
+            }
+        }
+        if style.effects.is_set(Effect::Underline) {
+            if let Some(color) = style.underline_color {
+                declarations.push(format!("text-decoration-color:{}", self.palette.hex(color)));
+            }
+        }
+
+        (classes, declarations)
+    }
+
+    fn render(&self, w: &mut impl io::Write, s: StyledStr<'_>) -> io::Result<()> {
+        if s.s.is_empty() {
+            return Ok(());
+        }
+        let link = s.link.as_ref().filter(|link| is_safe_url(&link.url));
+        if let Some(link) = link {
+            write!(w, "<a href=\"{}\">", escape(&link.url))?;
+        }
+        let (classes, declarations) = self.get_attributes(s.style.unwrap_or_default());
+        let is_span = !classes.is_empty() || !declarations.is_empty();
+        if is_span {
+            w.write_all(b"<span")?;
+            if !classes.is_empty() {
+                write!(w, " class=\"{}\"", escape(&classes.join(" ")))?;
+            }
+            if !declarations.is_empty() {
+                write!(w, " style=\"{}\"", escape(&declarations.join(";")))?;
+            }
+            w.write_all(b">")?;
+        }
+        w.write_all(escape(s.s).as_bytes())?;
+        if is_span {
+            w.write_all(b"</span>")?;
+        }
+        if link.is_some() {
+            w.write_all(b"</a>")?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns true if the given URL is relative or uses the `http`, `https` or `mailto` scheme.
+pub(crate) fn is_safe_url(url: &str) -> bool {
+    // browsers ignore leading control characters and spaces and all tabs and newlines in URLs
+    let url: String = url
+        .trim_start_matches(|c: char| c <= ' ')
+        .chars()
+        .filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+        .collect();
+    let scheme_end =
+        url.find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.')));
+    match scheme_end {
+        Some(i) if i > 0 && url[i..].starts_with(':') => {
+            let scheme = url[..i].to_ascii_lowercase();
+            scheme == "http" || scheme == "https" || scheme == "mailto"
+        }
+        _ => true,
+    }
+}
+
+/// An HTML tag.
+#[derive(Clone, Debug)]
+struct Tag {
+    name: String,
+    attributes: Vec<(String, String)>,
+    is_closing: bool,
+    is_self_closing: bool,
+    len: usize,
+}
+
+impl Tag {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// An open element on the stack of the HTML parser with the resulting style.
+#[derive(Clone, Debug)]
+struct Element {
+    name: String,
+    style: Style,
+    link: Option<Hyperlink<'static>>,
+    style_name: Option<String>,
+}
+
+impl Element {
+    fn new(tag: Tag, parent: Option<&Element>) -> Element {
+        let mut style = Style::default();
+        let mut link = None;
+        let mut style_name = None;
+        match tag.name.as_str() {
+            "b" | "strong" => style.effects.set(Effect::Bold, true),
+            "i" | "em" => style.effects.set(Effect::Italic, true),
+            "u" | "ins" => style.effects.set(Effect::Underline, true),
+            "s" | "strike" | "del" => style.effects.set(Effect::Strikethrough, true),
+            "code" => style_name = Some("code".to_owned()),
+            "a" => {
+                link = tag
+                    .attribute("href")
+                    .map(|url| Hyperlink::from(url.to_owned()))
+            }
+            "font" => style.fg = tag.attribute("color").and_then(parse_css_color),
+            _ => {}
+        }
+        if let Some(css) = tag.attribute("style") {
+            apply_css(&mut style, css);
+        }
+
+        if let Some(parent) = parent {
+            style = parent.style.and(style);
+            link = link.or_else(|| parent.link.clone());
+            style_name = style_name.or_else(|| parent.style_name.clone());
+        }
+        Element {
+            name: tag.name,
+            style,
+            link,
+            style_name,
+        }
+    }
+}
+
+/// Parses a HTML fragment into a styled text.
+///
+/// See the [module documentation](index.html) for a description of the supported elements.
+///
+/// # Example
+///
+/// ```
+/// use text_style::{html, StyledString};
+///
+/// let text = html::parse("<p>Run <code>make</code>, <em>then <blink>rest</blink></em>");
+/// assert_eq!(text.spans(), &[
+///     StyledString::plain("Run ".to_owned()),
+///     StyledString::themed("make".to_owned(), "code"),
+///     StyledString::plain(", ".to_owned()),
+///     StyledString::plain("then rest".to_owned()).italic(),
+/// ]);
+/// ```
+pub fn parse(s: &str) -> StyledText {
+    let mut spans = Vec::new();
+    let mut stack: Vec<Element> = Vec::new();
+    let mut current = String::new();
+    let mut rest = s;
+
+    while !rest.is_empty() {
+        let len = rest.find('<').unwrap_or(rest.len());
+        current.push_str(&unescape(&rest[..len]));
+        rest = &rest[len..];
+        if rest.is_empty() {
+            break;
+        }
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |idx| &comment[idx + 3..]);
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            rest = rest.find('>').map_or("", |idx| &rest[idx + 1..]);
+        } else if let Some(tag) = parse_tag(rest) {
+            rest = &rest[tag.len..];
+            if tag.is_closing {
+                if let Some(idx) = stack.iter().rposition(|e| e.name == tag.name) {
+                    push_span(&mut spans, &mut current, stack.last());
+                    stack.truncate(idx);
+                }
+            } else if tag.name == "br" {
+                current.push('\n');
+            } else if !tag.is_self_closing && !VOID_ELEMENTS.contains(&tag.name.as_str()) {
+                push_span(&mut spans, &mut current, stack.last());
+                let element = Element::new(tag, stack.last());
+                stack.push(element);
+            }
+        } else {
+            current.push('<');
+            rest = &rest[1..];
+        }
+    }
+    push_span(&mut spans, &mut current, stack.last());
+
+    spans.into_iter().collect()
+}
+
+/// Parses the tag at the start of the given string, or returns `None` if the string does not
+/// start with a valid tag.
+fn parse_tag(s: &str) -> Option<Tag> {
+    // find the end of the tag, ignoring > characters in quoted attribute values
+    let mut quote = None;
+    let mut end = None;
+    for (i, c) in s.char_indices().skip(1) {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if c == '>' => {
+                end = Some(i);
+                break;
+            }
+            None => {}
+        }
+    }
+    let end = end?;
+
+    let mut content = &s[1..end];
+    let is_closing = content.starts_with('/');
+    if is_closing {
+        content = &content[1..];
+    }
+    let is_self_closing = content.ends_with('/');
+    if is_self_closing {
+        content = &content[..content.len() - 1];
+    }
+
+    let name_len = content
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(content.len());
+    if name_len == 0 {
+        return None;
+    }
+    Some(Tag {
+        name: content[..name_len].to_ascii_lowercase(),
+        attributes: parse_attributes(&content[name_len..]),
+        is_closing,
+        is_self_closing,
+        len: end + 1,
+    })
+}
+
+fn parse_attributes(s: &str) -> Vec<(String, String)> {
+    let mut attributes = Vec::new();
+    let mut rest = s.trim_start();
+    while !rest.is_empty() {
+        let name_len = rest
+            .find(|c: char| c.is_whitespace() || c == '=')
+            .unwrap_or(rest.len());
+        let name = rest[..name_len].to_ascii_lowercase();
+        rest = rest[name_len..].trim_start();
+
+        let mut value = String::new();
+        if let Some(s) = rest.strip_prefix('=') {
+            let s = s.trim_start();
+            let (raw_value, s) = match s.chars().next() {
+                Some(q) if q == '"' || q == '\'' => {
+                    let s = &s[1..];
+                    let end = s.find(q).unwrap_or(s.len());
+                    (&s[..end], s.get(end + 1..).unwrap_or(""))
+                }
+                _ => {
+                    let end = s.find(char::is_whitespace).unwrap_or(s.len());
+                    (&s[..end], &s[end..])
+                }
+            };
+            value = unescape(raw_value);
+            rest = s.trim_start();
+        }
+        attributes.push((name, value));
+    }
+    attributes
+}
+
+/// Applies the supported declarations of the given inline CSS to the given style.
+fn apply_css(style: &mut Style, css: &str) {
+    for declaration in css.split(';') {
+        let idx = match declaration.find(':') {
+            Some(idx) => idx,
+            None => continue,
+        };
+        let property = declaration[..idx].trim().to_ascii_lowercase();
+        let value = declaration[idx + 1..].trim().to_ascii_lowercase();
+        match property.as_str() {
+            "color" => {
+                if let Some(color) = parse_css_color(&value) {
+                    style.fg = Some(color);
+                }
+            }
+            "background" | "background-color" => {
+                if let Some(color) = parse_css_color(&value) {
+                    style.bg = Some(color);
+                }
+            }
+            "font-weight" => {
+                let is_bold = matches!(value.as_str(), "bold" | "bolder")
+                    || matches!(value.parse::<u16>(), Ok(weight) if weight >= 600);
+                style.effects.set(Effect::Bold, is_bold);
+            }
+            "font-style" => {
+                let is_italic = matches!(value.as_str(), "italic" | "oblique");
+                style.effects.set(Effect::Italic, is_italic);
+            }
+            "text-decoration" | "text-decoration-line" => {
+                for word in value.split_whitespace() {
+                    match word {
+                        "underline" => style.effects.set(Effect::Underline, true),
+                        "line-through" => style.effects.set(Effect::Strikethrough, true),
+                        "overline" => style.effects.set(Effect::Overline, true),
+                        _ => {}
+                    }
+                }
+            }
+            "visibility" => style.effects.set(Effect::Hidden, value == "hidden"),
+            _ => {}
+        }
+    }
+}
+
+fn parse_css_color(s: &str) -> Option<Color> {
+    let s = s.trim().to_ascii_lowercase();
+    let (r, g, b) = if let Some(hex) = s.strip_prefix('#') {
+        if !hex.is_ascii() {
+            return None;
+        }
+        match hex.len() {
+            3 => {
+                let component = |i: usize| u8::from_str_radix(&hex[i..=i], 16).ok().map(|c| c * 17);
+                (component(0)?, component(1)?, component(2)?)
+            }
+            6 => {
+                let component = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+                (component(0)?, component(2)?, component(4)?)
+            }
+            _ => return None,
+        }
+    } else if let Some(args) = s
+        .strip_prefix("rgb(")
+        .or_else(|| s.strip_prefix("rgba("))
+        .and_then(|s| s.strip_suffix(')'))
+    {
+        let args: Vec<_> = args.split(',').map(str::trim).collect();
+        if args.len() != 3 && args.len() != 4 {
+            return None;
+        }
+        (
+            args[0].parse().ok()?,
+            args[1].parse().ok()?,
+            args[2].parse().ok()?,
+        )
+    } else {
+        CSS_COLORS
+            .iter()
+            .find(|(name, _)| *name == s)
+            .map(|(_, rgb)| *rgb)?
+    };
+    Some(Color::Rgb { r, g, b })
+}
+
+/// Decodes the character references in the given string.
+fn unescape(s: &str) -> String {
+    let mut unescaped = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(idx) = rest.find('&') {
+        unescaped.push_str(&rest[..idx]);
+        rest = &rest[idx..];
+        let reference = rest
+            .find(';')
+            .and_then(|end| decode_reference(&rest[1..end]).map(|c| (c, end)));
+        match reference {
+            Some((c, end)) => {
+                unescaped.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                unescaped.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    unescaped.push_str(rest);
+    unescaped
+}
+
+fn decode_reference(name: &str) -> Option<char> {
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some('\u{a0}'),
+        _ => {
+            let code =
+                if let Some(hex) = name.strip_prefix("#x").or_else(|| name.strip_prefix("#X")) {
+                    u32::from_str_radix(hex, 16).ok()?
+                } else {
+                    name.strip_prefix('#')?.parse().ok()?
+                };
+            std::char::from_u32(code)
+        }
+    }
+}
+
+fn push_span(spans: &mut Vec<StyledString>, s: &mut String, element: Option<&Element>) {
+    if s.is_empty() {
+        return;
+    }
+    let mut span = StyledString::plain(std::mem::take(s));
+    if let Some(element) = element {
+        if element.style != Style::default() {
+            span.style = Some(element.style);
+        }
+        span.link = element.link.clone();
+        span.style_name = element.style_name.clone();
+    }
+    match spans.last_mut() {
+        Some(last)
+            if last.style == span.style
+                && last.link == span.link
+                && last.style_name == span.style_name =>
+        {
+            last.s.push_str(&span.s);
+        }
+        _ => spans.push(span),
+    }
+}
+
+/// Renders a styled string to the given output as HTML using inline styles.
+///
+/// # Example
+///
+/// ```
+/// let s = text_style::StyledStr::plain("test").bold();
+/// text_style::html::render(std::io::stdout(), s)
+///     .expect("Failed to render string");
+/// ```
+pub fn render<'a>(w: impl io::Write, s: impl Into<StyledStr<'a>>) -> io::Result<()> {
+    render_iter(w, iter::once(s))
+}
+
+/// Renders a styled string to the given output as HTML using the given options.
+pub fn render_with<'a>(
+    w: impl io::Write,
+    s: impl Into<StyledStr<'a>>,
+    options: &Options,
+) -> io::Result<()> {
+    render_iter_with(w, iter::once(s), options)
+}
+
+/// Renders multiple styled string to the given output as HTML using inline styles.
+///
+/// # Example
+///
+/// ```
+/// let v = vec![
+///     text_style::StyledStr::plain("test").bold(),
+///     text_style::StyledStr::plain(" "),
+///     text_style::StyledStr::plain("test2").italic(),
+/// ];
+/// text_style::html::render_iter(std::io::stdout(), v.iter())
+///     .expect("Failed to render string");
+/// ```
+pub fn render_iter<'a, I, Iter, S, W>(w: W, iter: I) -> io::Result<()>
+where
+    I: IntoIterator<Item = S, IntoIter = Iter>,
+    Iter: Iterator<Item = S>,
+    S: Into<StyledStr<'a>>,
+    W: io::Write,
+{
+    render_iter_with(w, iter, &Options::new())
+}
+
+/// Renders multiple styled string to the given output as HTML using the given options.
+pub fn render_iter_with<'a, I, Iter, S, W>(mut w: W, iter: I, options: &Options) -> io::Result<()>
+where
+    I: IntoIterator<Item = S, IntoIter = Iter>,
+    Iter: Iterator<Item = S>,
+    S: Into<StyledStr<'a>>,
+    W: io::Write,
+{
+    for s in iter {
+        options.render(&mut w, s.into())?;
+    }
+    Ok(())
+}
+
+/// Escapes the special HTML characters in the given string.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(text_style::html::escape("<a href=\"#\">"), "&lt;a href=&quot;#&quot;&gt;");
+/// ```
+pub fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Returns the CSS property and value for the given effect.
+fn get_effect_declaration(effect: Effect) -> Option<(&'static str, &'static str)> {
+    match effect {
+        Effect::Bold => Some(("font-weight", "bold")),
+        Effect::Italic => Some(("font-style", "italic")),
+        Effect::Underline => Some(("text-decoration-line", "underline")),
+        Effect::Strikethrough => Some(("text-decoration-line", "line-through")),
+        Effect::Dim => Some(("opacity", "0.5")),
+        Effect::Hidden => Some(("visibility", "hidden")),
+        Effect::Overline => Some(("text-decoration-line", "overline")),
+        Effect::Reverse | Effect::Blink => None,
+    }
+}
+
+fn get_underline_style_value(style: UnderlineStyle) -> &'static str {
+    match style {
+        UnderlineStyle::Single => "solid",
+        UnderlineStyle::Double => "double",
+        UnderlineStyle::Curly => "wavy",
+        UnderlineStyle::Dotted => "dotted",
+        UnderlineStyle::Dashed => "dashed",
+    }
+}
@@ -0,0 +1,257 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+//! Style layers for cascading styles.
+//!
+//! A [`Style`][] can only describe which colors and effects are set, and [`Style::and`][] can only
+//! add effects to a style.  This module provides the [`StyleLayer`][] struct that can also
+//! explicitly unset a color or an effect, for example to disable the bold effect of a parent style
+//! for a part of the text.  Each setting of a layer has one of three states, see [`Setting`][]:
+//! it is inherited from the layers below, set or unset.
+//!
+//! A stack of layers can be flattened into a [`Style`][] using [`resolve`][].
+//!
+//! # Example
+//!
+//! ```
+//! use text_style::{layer::{self, StyleLayer}, AnsiColor, Effect, Style};
+//!
+//! let parent = StyleLayer::from(Style::fg(AnsiColor::Red.dark())).effect(Effect::Bold, true);
+//! let child = StyleLayer::new().effect(Effect::Bold, false).effect(Effect::Italic, true);
+//!
+//! assert_eq!(
+//!     layer::resolve(&[parent, child]),
+//!     Style::fg(AnsiColor::Red.dark()).and(Style::effect(Effect::Italic)),
+//! );
+//! ```
+//!
+//! [`Setting`]: enum.Setting.html
+//! [`Style`]: ../struct.Style.html
+//! [`Style::and`]: ../struct.Style.html#method.and
+//! [`StyleLayer`]: struct.StyleLayer.html
+//! [`resolve`]: fn.resolve.html
+
+use crate::{Color, Effect, Effects, Style, UnderlineStyle, EFFECTS};
+
+/// The state of a setting in a [`StyleLayer`][].
+///
+/// [`StyleLayer`]: struct.StyleLayer.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Setting<T> {
+    /// The setting is inherited from the layers below.
+    Inherit,
+    /// The setting is set to the given value.
+    Set(T),
+    /// The setting is reset to the default value.
+    Unset,
+}
+
+/// A style layer that can set, unset or inherit colors and text effects.
+///
+/// See the [module documentation](index.html) for more information.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StyleLayer {
+    /// The foreground color.
+    pub fg: Setting<Color>,
+    /// The background color.
+    pub bg: Setting<Color>,
+    /// The text effects that are set by this layer.
+    pub set_effects: Effects,
+    /// The text effects that are unset by this layer.
+    pub unset_effects: Effects,
+    /// The underline style.
+    pub underline_style: Setting<UnderlineStyle>,
+    /// The underline color.
+    pub underline_color: Setting<Color>,
+}
+
+impl<T> Setting<T> {
+    /// Applies this setting to the given value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use text_style::layer::Setting;
+    ///
+    /// assert_eq!(Setting::Inherit.apply(Some(1)), Some(1));
+    /// assert_eq!(Setting::Set(2).apply(Some(1)), Some(2));
+    /// assert_eq!(Setting::Unset.apply(Some(1)), None);
+    /// ```
+    pub fn apply(self, value: Option<T>) -> Option<T> {
+        match self {
+            Setting::Inherit => value,
+            Setting::Set(value) => Some(value),
+            Setting::Unset => None,
+        }
+    }
+
+    /// Combines this setting with another setting that is placed on top of this setting.
+    pub fn and(self, other: Setting<T>) -> Setting<T> {
+        match other {
+            Setting::Inherit => self,
+            other => other,
+        }
+    }
+
+    /// Checks whether this setting is inherited.
+    pub fn is_inherit(&self) -> bool {
+        matches!(self, Setting::Inherit)
+    }
+}
+
+impl<T> Default for Setting<T> {
+    fn default() -> Setting<T> {
+        Setting::Inherit
+    }
+}
+
+impl<T> From<T> for Setting<T> {
+    fn from(value: T) -> Setting<T> {
+        Setting::Set(value)
+    }
+}
+
+impl<T> From<Option<T>> for Setting<T> {
+    fn from(value: Option<T>) -> Setting<T> {
+        value.map_or(Setting::Inherit, Setting::Set)
+    }
+}
+
+impl StyleLayer {
+    /// Creates a new style layer that inherits all settings.
+    pub fn new() -> StyleLayer {
+        Default::default()
+    }
+
+    /// Sets the foreground color of this layer.
+    pub fn fg(mut self, fg: impl Into<Setting<Color>>) -> Self {
+        self.fg = fg.into();
+        self
+    }
+
+    /// Sets the background color of this layer.
+    pub fn bg(mut self, bg: impl Into<Setting<Color>>) -> Self {
+        self.bg = bg.into();
+        self
+    }
+
+    /// Sets or unsets the given text effect in this layer.
+    pub fn effect(mut self, effect: Effect, set: bool) -> Self {
+        self.set_effect(effect, set);
+        self
+    }
+
+    /// Sets or unsets the given text effect in this layer.
+    pub fn set_effect(&mut self, effect: Effect, set: bool) {
+        self.set_effects.set(effect, set);
+        self.unset_effects.set(effect, !set);
+    }
+
+    /// Inherits the given text effect from the layers below.
+    pub fn inherit_effect(&mut self, effect: Effect) {
+        self.set_effects.set(effect, false);
+        self.unset_effects.set(effect, false);
+    }
+
+    /// Returns the setting for the given text effect.
+    pub fn get_effect(&self, effect: Effect) -> Setting<()> {
+        if self.set_effects.is_set(effect) {
+            Setting::Set(())
+        } else if self.unset_effects.is_set(effect) {
+            Setting::Unset
+        } else {
+            Setting::Inherit
+        }
+    }
+
+    /// Combines this layer with another layer that is placed on top of this layer.
+    ///
+    /// All settings of the other layer that are not inherited overwrite the settings of this
+    /// layer.
+    pub fn and(mut self, layer: StyleLayer) -> StyleLayer {
+        self.fg = self.fg.and(layer.fg);
+        self.bg = self.bg.and(layer.bg);
+        for effect in EFFECTS {
+            match layer.get_effect(*effect) {
+                Setting::Set(()) => self.set_effect(*effect, true),
+                Setting::Unset => self.set_effect(*effect, false),
+                Setting::Inherit => {}
+            }
+        }
+        self.underline_style = self.underline_style.and(layer.underline_style);
+        self.underline_color = self.underline_color.and(layer.underline_color);
+        self
+    }
+
+    /// Applies this layer to the given style.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use text_style::{layer::{Setting, StyleLayer}, AnsiColor, Style};
+    ///
+    /// let layer = StyleLayer::new().fg(Setting::Unset);
+    /// assert_eq!(layer.apply(Style::fg(AnsiColor::Red.dark())), Style::default());
+    /// ```
+    pub fn apply(&self, mut style: Style) -> Style {
+        style.fg = self.fg.apply(style.fg);
+        style.bg = self.bg.apply(style.bg);
+        for effect in EFFECTS {
+            match self.get_effect(*effect) {
+                Setting::Set(()) => style.effects.set(*effect, true),
+                Setting::Unset => style.effects.set(*effect, false),
+                Setting::Inherit => {}
+            }
+        }
+        style.underline_style = self.underline_style.apply(style.underline_style);
+        style.underline_color = self.underline_color.apply(style.underline_color);
+        style
+    }
+}
+
+impl From<Style> for StyleLayer {
+    /// Creates a layer that sets all colors and effects that are set in the given style and
+    /// inherits all other settings.
+    fn from(style: Style) -> StyleLayer {
+        StyleLayer {
+            fg: style.fg.into(),
+            bg: style.bg.into(),
+            set_effects: style.effects,
+            unset_effects: Effects::empty(),
+            underline_style: style.underline_style.into(),
+            underline_color: style.underline_color.into(),
+        }
+    }
+}
+
+impl From<Effect> for StyleLayer {
+    fn from(effect: Effect) -> StyleLayer {
+        StyleLayer::new().effect(effect, true)
+    }
+}
+
+/// Flattens the given stack of layers into a style, starting with the default style.
+///
+/// The first layer is the bottom layer, i. e. the settings of the last layer have the highest
+/// priority.
+///
+/// # Example
+///
+/// ```
+/// use text_style::{layer::{self, Setting, StyleLayer}, AnsiColor, Style};
+///
+/// let layers = vec![
+///     StyleLayer::new().fg(AnsiColor::Red.dark()),
+///     StyleLayer::new().bg(AnsiColor::Blue.dark()),
+///     StyleLayer::new().fg(Setting::Unset),
+/// ];
+/// assert_eq!(layer::resolve(&layers), Style::bg(AnsiColor::Blue.dark()));
+/// ```
+pub fn resolve<'a, I>(layers: I) -> Style
+where
+    I: IntoIterator<Item = &'a StyleLayer>,
+{
+    layers
+        .into_iter()
+        .fold(Style::default(), |style, layer| layer.apply(style))
+}
@@ -8,18 +8,32 @@
 //! The central types of this crate are [`StyledStr`][] and [`StyledString`][]:  owned and borrowed
 //! strings that are annotated with an optional style information, [`Style`][].  This style
 //! information consists of foreground and background colors ([`Color`][]) and multiple effects
-//! ([`Effect`][]: bold, italic, underline or strikeout).
+//! ([`Effect`][]: bold, italic, underline, strikeout, dim, reverse, blink, hidden or overline).
+//! Styled strings can also carry a [`Hyperlink`][].  Sequences of styled strings can be stored in
+//! a [`StyledText`][].
 //!
 //! `text_style`’s types can be created directly or converted from or to several formats (all
 //! optional and activated by features):
 //!
-//! - [`ansi_term`][]: convert to [`ansi_term::ANSIString`][]
-//! - [`crossterm`][]: convert to [`crossterm::style::StyledContent`][]
-//! - [`cursive`][]: convert to [`cursive::utils::markup::StyledString`][]
+//! - [`ansi_term`][]: convert from and to [`ansi_term::ANSIString`][]
+//! - [`crossterm`][]: convert from and to [`crossterm::style::StyledContent`][]
+//! - [`cursive`][]: convert from and to [`cursive::utils::markup::StyledString`][]
 //! - [`genpdf`][]: convert to [`genpdf::style::StyledStr`][] and [`genpdf::style::StyledString`][]
+//! - [`pulldown_cmark`][]: convert from Markdown
 //! - [`syntect`][]: convert from [`syntect::highlighting::Style`][]
 //! - [`termion`][]: convert to a termion escape string
 //!
+//! Text with ANSI escape sequences can be parsed into styled text using the [`ansi`][] module.
+//! Styles that can unset colors and effects of a parent style are provided by the [`layer`][]
+//! module, and semantic styles that are looked up by name are provided by the [`theme`][] module.
+//! Styled text can be written using a simple inline markup language, see the [`markup`][] module.
+//! The [`html`][] module parses and renders HTML, and the [`svg`][] module renders styled text as
+//! an SVG image.  The display width of styled text can be measured and truncated using the
+//! [`width`][] module, and styled text can be wrapped using the [`wrap`][] module.  Cells of
+//! styled text can be laid out in a table using the [`table`][] module.  These modules are always
+//! available and depend on the `unicode-segmentation` and `unicode-width` crates, which are the
+//! only required dependencies of this crate.
+//!
 //! # Background
 //!
 //! There is a plethora of crates that produce or consume styled text.  Most of these crates use
@@ -79,19 +93,35 @@
 //! text_style::termion::render(&mut w, &s).expect("Rendering failed");
 //! ```
 //!
+//! The supported colors and effects of a terminal can be detected using the [`terminal`][] module
+//! and applied using the `render_with` and `render_iter_with` methods.
+//!
 //! For more information, see the module documentations.
 //!
 //! [`Color`]: enum.Color.html
 //! [`Effect`]: enum.Effect.html
+//! [`Hyperlink`]: struct.Hyperlink.html
 //! [`Style`]: struct.Style.html
 //! [`StyledStr`]: struct.StyledStr.html
 //! [`StyledString`]: struct.StyledString.html
+//! [`StyledText`]: struct.StyledText.html
+//! [`ansi`]: ./ansi/index.html
 //! [`ansi_term`]: ./ansi_term/index.html
 //! [`crossterm`]: ./crossterm/index.html
 //! [`cursive`]: ./cursive/index.html
 //! [`genpdf`]: ./genpdf/index.html
+//! [`html`]: ./html/index.html
+//! [`layer`]: ./layer/index.html
+//! [`markup`]: ./markup/index.html
+//! [`pulldown_cmark`]: ./pulldown_cmark/index.html
+//! [`svg`]: ./svg/index.html
 //! [`syntect`]: ./syntect/index.html
+//! [`table`]: ./table/index.html
+//! [`terminal`]: ./terminal/index.html
 //! [`termion`]: ./termion/index.html
+//! [`theme`]: ./theme/index.html
+//! [`width`]: ./width/index.html
+//! [`wrap`]: ./wrap/index.html
 //! [`ansi_term::ANSIString`]: https://docs.rs/ansi_term/latest/ansi_term/type.ANSIString.html
 //! [`crossterm::style::StyledContent`]: https://docs.rs/crossterm/latest/crossterm/style/struct.StyledContent.html
 //! [`cursive::utils::markup::StyledString`]: https://docs.rs/cursive/latest/cursive/utils/markup/type.StyledString.html
@@ -101,6 +131,7 @@
 
 #![warn(missing_docs, rust_2018_idioms)]
 
+pub mod ansi;
 #[cfg(feature = "ansi_term")]
 pub mod ansi_term;
 #[cfg(feature = "crossterm")]
@@ -109,10 +140,29 @@ pub mod crossterm;
 pub mod cursive;
 #[cfg(feature = "genpdf")]
 pub mod genpdf;
+pub mod html;
+pub mod layer;
+pub mod markup;
+#[cfg(feature = "pulldown-cmark")]
+pub mod pulldown_cmark;
+pub mod svg;
 #[cfg(feature = "syntect")]
 pub mod syntect;
+pub mod table;
+pub mod terminal;
 #[cfg(feature = "termion")]
 pub mod termion;
+pub mod theme;
+pub mod width;
+pub mod wrap;
+
+mod palette;
+mod spec;
+
+use std::borrow::Cow;
+use std::fmt;
+use std::iter;
+use std::ops;
 
 /// A borrowed string with an optional style annotation.
 ///
@@ -131,6 +181,15 @@ pub struct StyledStr<'a> {
     pub s: &'a str,
     /// The style of this string.
     pub style: Option<Style>,
+    /// The hyperlink target of this string.
+    pub link: Option<Hyperlink<'a>>,
+    /// The name of the semantic style of this string, see [`Theme`][].
+    ///
+    /// The name is resolved by the `render_themed` and `render_iter_themed` functions of the
+    /// terminal backends.  All other conversion and render functions ignore it.
+    ///
+    /// [`Theme`]: theme/struct.Theme.html
+    pub style_name: Option<&'a str>,
 }
 
 /// An owned string with an optional style annotation.
@@ -152,10 +211,98 @@ pub struct StyledString {
     pub s: String,
     /// The style of this string.
     pub style: Option<Style>,
+    /// The hyperlink target of this string.
+    pub link: Option<Hyperlink<'static>>,
+    /// The name of the semantic style of this string, see [`Theme`][].
+    ///
+    /// The name is resolved by the `render_themed` and `render_iter_themed` functions of the
+    /// terminal backends.  All other conversion and render functions ignore it.
+    ///
+    /// [`Theme`]: theme/struct.Theme.html
+    pub style_name: Option<String>,
+}
+
+/// An owned sequence of styled strings (spans).
+///
+/// The spans can be rendered in one call by passing a reference to this struct to the
+/// `render_iter` functions of the backends.
+///
+/// # Example
+///
+/// ```
+/// use text_style::{AnsiColor, StyledString, StyledText};
+///
+/// let mut text = StyledText::new();
+/// text.push(StyledString::plain("error".to_owned()).with(AnsiColor::Red.light()));
+/// text.push(StyledString::plain(": file not found".to_owned()));
+///
+/// assert_eq!(text.len(), 2);
+/// assert_eq!(text.to_string(), "error: file not found");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StyledText {
+    spans: Vec<StyledString>,
+}
+
+/// A hyperlink target, consisting of a URL and an optional ID.
+///
+/// Terminals use the ID to identify multiple strings that belong to the same link, for example
+/// a link that is split over multiple lines.  Hyperlinks are not supported by all backends.  If
+/// they are not supported, they are ignored.
+///
+/// # Example
+///
+/// ```
+/// use text_style::{Hyperlink, StyledStr};
+///
+/// let s1 = StyledStr::plain("docs.rs").link("https://docs.rs");
+/// let s2 = StyledStr::plain("docs.rs").link(Hyperlink::new("https://docs.rs"));
+/// assert_eq!(s1, s2);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hyperlink<'a> {
+    /// The URL of the link target.
+    pub url: Cow<'a, str>,
+    /// The ID of the link (if set).
+    pub id: Option<Cow<'a, str>>,
 }
 
 /// A text style, a combination of a foreground color, a background color and text effects (all
 /// optional).
+///
+/// If the underline effect is set, the style can also define the style and the color of the
+/// underline.  These settings are not supported by all backends.  If they are not supported, a
+/// plain underline with the foreground color is used instead.
+///
+/// # Parsing and formatting
+///
+/// Styles implement [`FromStr`][] and [`Display`][] using a space-separated list of these words:
+///
+/// - the effects `bold`, `italic`, `underline` (or `ul`), `strikethrough` (or `strike`), `dim`,
+///   `reverse`, `blink`, `hidden` and `overline`
+/// - the underline styles `double-underline`, `curly-underline`, `dotted-underline` and
+///   `dashed-underline` (these also set the underline effect)
+/// - a foreground color
+/// - `on` followed by a background color
+/// - `underline-color` followed by an underline color
+///
+/// See [`Color`][] for the format of the colors.
+///
+/// ```
+/// use text_style::{AnsiColor, Color, Style};
+///
+/// let style: Style = "bold italic #ff8800 on 236".parse().expect("Invalid style");
+/// assert!(style.effects.is_bold && style.effects.is_italic);
+/// assert_eq!(style.fg, Some(Color::Rgb { r: 255, g: 136, b: 0 }));
+/// assert_eq!(style.bg, Some(Color::Indexed(236)));
+///
+/// let style: Style = "red dim ul".parse().expect("Invalid style");
+/// assert_eq!(style.to_string(), "underline dim red");
+/// ```
+///
+/// [`Color`]: enum.Color.html
+/// [`Display`]: https://doc.rust-lang.org/std/fmt/trait.Display.html
+/// [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct Style {
     /// The foreground color (if set).
@@ -164,6 +311,10 @@ pub struct Style {
     pub bg: Option<Color>,
     /// The text effects.
     pub effects: Effects,
+    /// The underline style (if set).  If it is not set, a single underline is used.
+    pub underline_style: Option<UnderlineStyle>,
+    /// The underline color (if set).  If it is not set, the foreground color is used.
+    pub underline_color: Option<Color>,
 }
 
 /// A text effect.
@@ -177,6 +328,31 @@ pub enum Effect {
     Underline,
     /// Struckthrough text.
     Strikethrough,
+    /// Dimmed (faint) text.
+    Dim,
+    /// Text with reversed foreground and background colors.
+    Reverse,
+    /// Blinking text.
+    Blink,
+    /// Hidden (concealed) text.
+    Hidden,
+    /// Overlined text.
+    Overline,
+}
+
+/// The style of an underline.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UnderlineStyle {
+    /// A single straight line.
+    Single,
+    /// A double straight line.
+    Double,
+    /// A curly line.
+    Curly,
+    /// A dotted line.
+    Dotted,
+    /// A dashed line.
+    Dashed,
 }
 
 /// All available text effects.
@@ -185,6 +361,11 @@ pub const EFFECTS: &[Effect] = &[
     Effect::Italic,
     Effect::Underline,
     Effect::Strikethrough,
+    Effect::Dim,
+    Effect::Reverse,
+    Effect::Blink,
+    Effect::Hidden,
+    Effect::Overline,
 ];
 
 /// A set of text effects.
@@ -198,6 +379,16 @@ pub struct Effects {
     pub is_underline: bool,
     /// Whether the strikethrough text effect is set.
     pub is_strikethrough: bool,
+    /// Whether the dim text effect is set.
+    pub is_dim: bool,
+    /// Whether the reverse text effect is set.
+    pub is_reverse: bool,
+    /// Whether the blink text effect is set.
+    pub is_blink: bool,
+    /// Whether the hidden text effect is set.
+    pub is_hidden: bool,
+    /// Whether the overline text effect is set.
+    pub is_overline: bool,
 }
 
 /// An iterator over text effects.
@@ -209,11 +400,31 @@ pub struct EffectsIter {
 
 /// A color.
 ///
-/// This enum stores colors, either as an ANSI color (see [`AnsiColor`][] and [`AnsiMode`][]) or as
-/// an RGB color.
+/// This enum stores colors, either as an ANSI color (see [`AnsiColor`][] and [`AnsiMode`][]), as an
+/// index into the 256-color palette of xterm-compatible terminals or as an RGB color.
+///
+/// # Parsing and formatting
+///
+/// Colors implement [`FromStr`][] and [`Display`][] using these formats:
+///
+/// - the names of the ANSI colors, `black`, `red`, `green`, `yellow`, `blue`, `magenta`, `cyan`
+///   and `white`, for the dark ANSI colors and the names with the `bright-` prefix, for example
+///   `bright-red`, for the light ANSI colors
+/// - the index for colors from the 256-color palette, for example `208`
+/// - `#rrggbb` or `rgb(r,g,b)` for RGB colors, for example `#ff8800` or `rgb(255,136,0)`
+///
+/// ```
+/// use text_style::{AnsiColor, Color};
+///
+/// assert_eq!("bright-red".parse(), Ok(AnsiColor::Red.light()));
+/// assert_eq!("rgb(255, 136, 0)".parse(), Ok(Color::Rgb { r: 255, g: 136, b: 0 }));
+/// assert_eq!(Color::Rgb { r: 255, g: 136, b: 0 }.to_string(), "#ff8800");
+/// ```
 ///
 /// [`AnsiColor`]: enum.AnsiColor.html
 /// [`AnsiMode`]: enum.AnsiMode.html
+/// [`Display`]: https://doc.rust-lang.org/std/fmt/trait.Display.html
+/// [`FromStr`]: https://doc.rust-lang.org/std/str/trait.FromStr.html
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Color {
     /// An ANSI color.
@@ -223,6 +434,11 @@ pub enum Color {
         /// The variant of the ANSI base color (light or dark).
         mode: AnsiMode,
     },
+    /// A color from the 256-color palette.
+    ///
+    /// The indices 0–15 refer to the ANSI colors, 16–231 to a 6×6×6 RGB color cube and 232–255 to
+    /// a grayscale ramp.
+    Indexed(u8),
     /// An RGB color.
     Rgb {
         /// The red component.
@@ -234,6 +450,50 @@ pub enum Color {
     },
 }
 
+/// An error that occured when parsing a [`Style`][] or a [`Color`][].
+///
+/// [`Color`]: enum.Color.html
+/// [`Style`]: struct.Style.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseStyleError {
+    /// The byte offset of the invalid word in the input string.
+    pub offset: usize,
+    /// The invalid word.
+    pub text: String,
+}
+
+/// An error that occured when converting a type of another crate that uses attributes that cannot
+/// be represented by the types of this crate.
+///
+/// The error contains the converted value without the unsupported attributes, so it can also be
+/// used for lossy conversions.
+///
+/// # Example
+///
+/// Converting a `crossterm` style (requires the `crossterm` feature):
+///
+#[cfg_attr(feature = "crossterm", doc = "```")]
+#[cfg_attr(not(feature = "crossterm"), doc = "```ignore")]
+/// use std::convert::TryFrom;
+/// use crossterm::style::{Attribute, ContentStyle};
+/// use text_style::{Effect, Style};
+///
+/// let mut content_style = ContentStyle::new();
+/// content_style.attributes.set(Attribute::Bold);
+/// content_style.attributes.set(Attribute::Fraktur);
+///
+/// let err = Style::try_from(content_style).unwrap_err();
+/// assert_eq!(err.unsupported, vec!["Fraktur".to_owned()]);
+/// assert_eq!(err.value, Style::effect(Effect::Bold));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConversionError<T> {
+    /// The converted value without the unsupported attributes.
+    pub value: T,
+    /// The names of the unsupported attributes.
+    pub unsupported: Vec<String>,
+}
+
 /// An ANSI base color.
 ///
 /// This enum contains the basic eight ANSI colors.  These colors are available in two modes:
@@ -263,6 +523,45 @@ pub enum AnsiColor {
     White,
 }
 
+/// All ANSI base colors, ordered by their color number.
+pub const ANSI_COLORS: &[AnsiColor] = &[
+    AnsiColor::Black,
+    AnsiColor::Red,
+    AnsiColor::Green,
+    AnsiColor::Yellow,
+    AnsiColor::Blue,
+    AnsiColor::Magenta,
+    AnsiColor::Cyan,
+    AnsiColor::White,
+];
+
+/// An RGB palette for the ANSI colors.
+///
+/// Terminals use different RGB values for the ANSI colors.  This struct stores the RGB values that
+/// are used by backends that need RGB colors, for example [`html`][].  The default palette is the
+/// VGA palette.
+///
+/// # Example
+///
+/// ```
+/// use text_style::{AnsiColor, AnsiMode, Color, Palette};
+///
+/// let mut palette = Palette::vga();
+/// assert_eq!(palette.rgb(AnsiColor::Red.dark()), (170, 0, 0));
+/// palette.set(AnsiColor::Red, AnsiMode::Dark, (205, 0, 0));
+/// assert_eq!(palette.rgb(AnsiColor::Red.dark()), (205, 0, 0));
+/// assert_eq!(palette.rgb(Color::Indexed(208)), (255, 135, 0));
+/// ```
+///
+/// [`html`]: html/index.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Palette {
+    /// The RGB values of the dark ANSI colors, ordered by their color number.
+    pub dark: [(u8, u8, u8); 8],
+    /// The RGB values of the light ANSI colors, ordered by their color number.
+    pub light: [(u8, u8, u8); 8],
+}
+
 /// An ANSI color mode.
 ///
 /// The ANSI base colors, stored in the [`AnsiColor`][] enum, are available in two modes:
@@ -281,10 +580,38 @@ pub enum AnsiMode {
     Light,
 }
 
+/// A color depth, i. e. the set of colors that can be displayed by a terminal.
+///
+/// The color depths are ordered by the number of supported colors.  Colors can be converted to a
+/// lower color depth using the `downgrade` methods of [`Color`][], [`Style`][], [`StyledStr`][],
+/// [`StyledString`][] and [`StyledText`][].
+///
+/// [`Color`]: enum.Color.html
+/// [`Style`]: struct.Style.html
+/// [`StyledStr`]: struct.StyledStr.html
+/// [`StyledString`]: struct.StyledString.html
+/// [`StyledText`]: struct.StyledText.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorDepth {
+    /// No colors.
+    NoColor,
+    /// The 16 ANSI colors.
+    Ansi,
+    /// The 256-color palette.
+    Indexed,
+    /// 24-bit RGB colors.
+    TrueColor,
+}
+
 impl<'a> StyledStr<'a> {
     /// Creates a new styled string from the given string and an optional style.
     pub fn new(s: &'a str, style: Option<Style>) -> StyledStr<'a> {
-        StyledStr { s, style }
+        StyledStr {
+            s,
+            style,
+            link: None,
+            style_name: None,
+        }
     }
 
     /// Creates a new styled string from the given string and style.
@@ -297,6 +624,29 @@ impl<'a> StyledStr<'a> {
         StyledStr::new(s, None)
     }
 
+    /// Creates a new styled string from the given string with the semantic style with the given
+    /// name.
+    ///
+    /// The style is resolved when the string is passed to [`Theme::apply`][] or to one of the
+    /// `render_themed` functions of the terminal backends.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use text_style::{theme::Theme, AnsiColor, Style, StyledStr};
+    ///
+    /// let theme = Theme::new().with("error", Style::fg(AnsiColor::Red.light()));
+    /// let s = theme.apply(StyledStr::themed("error", "error"));
+    /// assert_eq!(s.style, Some(Style::fg(AnsiColor::Red.light())));
+    /// ```
+    ///
+    /// [`Theme::apply`]: theme/struct.Theme.html#method.apply
+    pub fn themed(s: &'a str, style_name: &'a str) -> StyledStr<'a> {
+        let mut s = StyledStr::plain(s);
+        s.style_name = Some(style_name);
+        s
+    }
+
     /// Sets the foreground color for this styled string.
     pub fn with(mut self, fg: Color) -> Self {
         self.style_mut().fg = Some(fg);
@@ -329,23 +679,85 @@ impl<'a> StyledStr<'a> {
         self.effect(Effect::Strikethrough)
     }
 
+    /// Sets the underline effect with the given underline style for this styled string.
+    pub fn underline_style(mut self, underline_style: UnderlineStyle) -> Self {
+        self.style_mut().set_underline_style(underline_style);
+        self.underline()
+    }
+
+    /// Sets the underline color for this styled string.
+    ///
+    /// Note that this does not set the underline effect, see [`underline`][].
+    ///
+    /// [`underline`]: #method.underline
+    pub fn underline_color(mut self, color: Color) -> Self {
+        self.style_mut().set_underline_color(color);
+        self
+    }
+
+    /// Sets the dim effect for this styled string.
+    pub fn dim(self) -> Self {
+        self.effect(Effect::Dim)
+    }
+
+    /// Sets the reverse effect for this styled string.
+    pub fn reverse(self) -> Self {
+        self.effect(Effect::Reverse)
+    }
+
+    /// Sets the blink effect for this styled string.
+    pub fn blink(self) -> Self {
+        self.effect(Effect::Blink)
+    }
+
+    /// Sets the hidden effect for this styled string.
+    pub fn hidden(self) -> Self {
+        self.effect(Effect::Hidden)
+    }
+
+    /// Sets the overline effect for this styled string.
+    pub fn overline(self) -> Self {
+        self.effect(Effect::Overline)
+    }
+
     /// Sets the given effect for this styled string.
     pub fn effect(mut self, effect: Effect) -> Self {
         self.style_mut().effects.set(effect, true);
         self
     }
 
+    /// Sets the hyperlink target for this styled string.
+    pub fn link(mut self, link: impl Into<Hyperlink<'a>>) -> Self {
+        self.link = Some(link.into());
+        self
+    }
+
     /// Returns a mutable reference to the style of this string, creating a new style with the
     /// default settings if the style is currently `None`.
     pub fn style_mut(&mut self) -> &mut Style {
         self.style.get_or_insert_with(Default::default)
     }
+
+    /// Converts the colors of this string to the given color depth.
+    ///
+    /// See [`Color::downgrade`][] for more information.
+    ///
+    /// [`Color::downgrade`]: enum.Color.html#method.downgrade
+    pub fn downgrade(mut self, depth: ColorDepth) -> Self {
+        self.style = self.style.map(|style| style.downgrade(depth));
+        self
+    }
 }
 
 impl StyledString {
     /// Creates a new styled string from the given string and an optional style.
     pub fn new(s: String, style: Option<Style>) -> StyledString {
-        StyledString { s, style }
+        StyledString {
+            s,
+            style,
+            link: None,
+            style_name: None,
+        }
     }
 
     /// Creates a new styled string from the given string and style.
@@ -358,6 +770,19 @@ impl StyledString {
         StyledString::new(s, None)
     }
 
+    /// Creates a new styled string from the given string with the semantic style with the given
+    /// name.
+    ///
+    /// The style is resolved when the string is passed to [`Theme::apply_string`][] or to one of
+    /// the `render_themed` functions of the terminal backends.
+    ///
+    /// [`Theme::apply_string`]: theme/struct.Theme.html#method.apply_string
+    pub fn themed(s: String, style_name: impl Into<String>) -> StyledString {
+        let mut s = StyledString::plain(s);
+        s.style_name = Some(style_name.into());
+        s
+    }
+
     /// Sets the foreground color for this styled string.
     pub fn with(mut self, fg: Color) -> Self {
         self.style_mut().fg = Some(fg);
@@ -390,25 +815,140 @@ impl StyledString {
         self.effect(Effect::Strikethrough)
     }
 
+    /// Sets the underline effect with the given underline style for this styled string.
+    pub fn underline_style(mut self, underline_style: UnderlineStyle) -> Self {
+        self.style_mut().set_underline_style(underline_style);
+        self.underline()
+    }
+
+    /// Sets the underline color for this styled string.
+    ///
+    /// Note that this does not set the underline effect, see [`underline`][].
+    ///
+    /// [`underline`]: #method.underline
+    pub fn underline_color(mut self, color: Color) -> Self {
+        self.style_mut().set_underline_color(color);
+        self
+    }
+
+    /// Sets the dim effect for this styled string.
+    pub fn dim(self) -> Self {
+        self.effect(Effect::Dim)
+    }
+
+    /// Sets the reverse effect for this styled string.
+    pub fn reverse(self) -> Self {
+        self.effect(Effect::Reverse)
+    }
+
+    /// Sets the blink effect for this styled string.
+    pub fn blink(self) -> Self {
+        self.effect(Effect::Blink)
+    }
+
+    /// Sets the hidden effect for this styled string.
+    pub fn hidden(self) -> Self {
+        self.effect(Effect::Hidden)
+    }
+
+    /// Sets the overline effect for this styled string.
+    pub fn overline(self) -> Self {
+        self.effect(Effect::Overline)
+    }
+
     /// Sets the given effect for this styled string.
     pub fn effect(mut self, effect: Effect) -> Self {
         self.style_mut().effects.set(effect, true);
         self
     }
 
+    /// Sets the hyperlink target for this styled string.
+    pub fn link(mut self, link: impl Into<Hyperlink<'static>>) -> Self {
+        self.link = Some(link.into());
+        self
+    }
+
     /// Returns a mutable reference to the style of this string, creating a new style with the
     /// default settings if the style is currently `None`.
     pub fn style_mut(&mut self) -> &mut Style {
         self.style.get_or_insert_with(Default::default)
     }
+
+    /// Converts the colors of this string to the given color depth.
+    ///
+    /// See [`Color::downgrade`][] for more information.
+    ///
+    /// [`Color::downgrade`]: enum.Color.html#method.downgrade
+    pub fn downgrade(mut self, depth: ColorDepth) -> Self {
+        self.style = self.style.map(|style| style.downgrade(depth));
+        self
+    }
+}
+
+impl StyledText {
+    /// Creates a new styled text without any spans.
+    pub fn new() -> StyledText {
+        Default::default()
+    }
+
+    /// Appends a span to the end of this text.
+    pub fn push(&mut self, s: impl Into<StyledString>) {
+        self.spans.push(s.into());
+    }
+
+    /// Appends the given text to the end of this text.
+    pub fn append(&mut self, mut text: StyledText) {
+        self.spans.append(&mut text.spans);
+    }
+
+    /// Returns the number of spans in this text.
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    /// Checks whether this text does not contain any spans.
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Returns the spans of this text.
+    pub fn spans(&self) -> &[StyledString] {
+        &self.spans
+    }
+
+    /// Returns a mutable reference to the spans of this text.
+    pub fn spans_mut(&mut self) -> &mut Vec<StyledString> {
+        &mut self.spans
+    }
+
+    /// Converts this text into its spans.
+    pub fn into_spans(self) -> Vec<StyledString> {
+        self.spans
+    }
+
+    /// Returns an iterator over the spans of this text.
+    pub fn iter(&self) -> std::slice::Iter<'_, StyledString> {
+        self.spans.iter()
+    }
+
+    /// Returns the content of this text without the style information.
+    pub fn plain_text(&self) -> String {
+        self.spans.iter().map(|s| s.s.as_str()).collect()
+    }
+
+    /// Converts the colors of this text to the given color depth.
+    ///
+    /// See [`Color::downgrade`][] for more information.
+    ///
+    /// [`Color::downgrade`]: enum.Color.html#method.downgrade
+    pub fn downgrade(self, depth: ColorDepth) -> StyledText {
+        self.into_iter().map(|s| s.downgrade(depth)).collect()
+    }
 }
 
 impl<'a, 'b> From<&'b StyledStr<'a>> for StyledStr<'a> {
     fn from(s: &'b StyledStr<'a>) -> StyledStr<'a> {
-        StyledStr {
-            s: &s.s,
-            style: s.style,
-        }
+        s.clone()
     }
 }
 
@@ -417,6 +957,8 @@ impl<'a> From<&'a StyledString> for StyledStr<'a> {
         StyledStr {
             s: &s.s,
             style: s.style,
+            link: s.link.as_ref().map(Hyperlink::as_borrowed),
+            style_name: s.style_name.as_deref(),
         }
     }
 }
@@ -426,6 +968,8 @@ impl<'a> From<StyledStr<'a>> for StyledString {
         StyledString {
             s: s.s.to_owned(),
             style: s.style,
+            link: s.link.map(Hyperlink::into_owned),
+            style_name: s.style_name.map(ToOwned::to_owned),
         }
     }
 }
@@ -442,10 +986,160 @@ impl From<String> for StyledString {
     }
 }
 
+impl<'a> From<StyledStr<'a>> for StyledText {
+    fn from(s: StyledStr<'a>) -> StyledText {
+        StyledText::from(StyledString::from(s))
+    }
+}
+
+impl From<StyledString> for StyledText {
+    fn from(s: StyledString) -> StyledText {
+        StyledText { spans: vec![s] }
+    }
+}
+
+impl From<Vec<StyledString>> for StyledText {
+    fn from(spans: Vec<StyledString>) -> StyledText {
+        StyledText { spans }
+    }
+}
+
+impl From<StyledText> for Vec<StyledString> {
+    fn from(text: StyledText) -> Vec<StyledString> {
+        text.spans
+    }
+}
+
+impl fmt::Display for StyledText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for s in &self.spans {
+            f.write_str(&s.s)?;
+        }
+        Ok(())
+    }
+}
+
+impl<S: Into<StyledString>> iter::Extend<S> for StyledText {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        self.spans.extend(iter.into_iter().map(Into::into));
+    }
+}
+
+impl<S: Into<StyledString>> iter::FromIterator<S> for StyledText {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> StyledText {
+        let mut text = StyledText::new();
+        text.extend(iter);
+        text
+    }
+}
+
+impl IntoIterator for StyledText {
+    type Item = StyledString;
+    type IntoIter = std::vec::IntoIter<StyledString>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.spans.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a StyledText {
+    type Item = &'a StyledString;
+    type IntoIter = std::slice::Iter<'a, StyledString>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.spans.iter()
+    }
+}
+
+impl<S: Into<StyledString>> ops::Add<S> for StyledText {
+    type Output = StyledText;
+
+    fn add(mut self, s: S) -> StyledText {
+        self += s;
+        self
+    }
+}
+
+impl ops::Add<StyledText> for StyledText {
+    type Output = StyledText;
+
+    fn add(mut self, text: StyledText) -> StyledText {
+        self += text;
+        self
+    }
+}
+
+impl<S: Into<StyledString>> ops::AddAssign<S> for StyledText {
+    fn add_assign(&mut self, s: S) {
+        self.push(s);
+    }
+}
+
+impl ops::AddAssign<StyledText> for StyledText {
+    fn add_assign(&mut self, text: StyledText) {
+        self.append(text);
+    }
+}
+
+impl<'a> Hyperlink<'a> {
+    /// Creates a new hyperlink to the given URL without an ID.
+    pub fn new(url: impl Into<Cow<'a, str>>) -> Hyperlink<'a> {
+        Hyperlink {
+            url: url.into(),
+            id: None,
+        }
+    }
+
+    /// Sets the ID of this hyperlink.
+    pub fn with_id(mut self, id: impl Into<Cow<'a, str>>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Returns a hyperlink that borrows the URL and the ID of this hyperlink.
+    pub fn as_borrowed(&self) -> Hyperlink<'_> {
+        Hyperlink {
+            url: Cow::Borrowed(&self.url),
+            id: self.id.as_deref().map(Cow::Borrowed),
+        }
+    }
+
+    /// Converts this hyperlink into an owned hyperlink.
+    pub fn into_owned(self) -> Hyperlink<'static> {
+        Hyperlink {
+            url: Cow::Owned(self.url.into_owned()),
+            id: self.id.map(|id| Cow::Owned(id.into_owned())),
+        }
+    }
+}
+
+impl<'a> From<&'a str> for Hyperlink<'a> {
+    fn from(url: &'a str) -> Hyperlink<'a> {
+        Hyperlink::new(url)
+    }
+}
+
+impl From<String> for Hyperlink<'static> {
+    fn from(url: String) -> Hyperlink<'static> {
+        Hyperlink::new(url)
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for Hyperlink<'a> {
+    fn from(url: Cow<'a, str>) -> Hyperlink<'a> {
+        Hyperlink::new(url)
+    }
+}
+
 impl Style {
     /// Creates a new style with the given foreground and background colors and effects.
     pub fn new(fg: Option<Color>, bg: Option<Color>, effects: Effects) -> Style {
-        Style { fg, bg, effects }
+        Style {
+            fg,
+            bg,
+            effects,
+            ..Default::default()
+        }
     }
 
     /// Creates a new style with the given foreground color.
@@ -490,6 +1184,12 @@ impl Style {
             self.bg = Some(bg);
         }
         self.effects = self.effects.and(style.effects);
+        if let Some(underline_style) = style.underline_style {
+            self.underline_style = Some(underline_style);
+        }
+        if let Some(underline_color) = style.underline_color {
+            self.underline_color = Some(underline_color);
+        }
         self
     }
 
@@ -523,10 +1223,76 @@ impl Style {
         self.effects.is_strikethrough = strikethrough;
     }
 
+    /// Sets or unsets the dim effect for this style.
+    pub fn set_dim(&mut self, dim: bool) {
+        self.effects.is_dim = dim;
+    }
+
+    /// Sets or unsets the reverse effect for this style.
+    pub fn set_reverse(&mut self, reverse: bool) {
+        self.effects.is_reverse = reverse;
+    }
+
+    /// Sets or unsets the blink effect for this style.
+    pub fn set_blink(&mut self, blink: bool) {
+        self.effects.is_blink = blink;
+    }
+
+    /// Sets or unsets the hidden effect for this style.
+    pub fn set_hidden(&mut self, hidden: bool) {
+        self.effects.is_hidden = hidden;
+    }
+
+    /// Sets or unsets the overline effect for this style.
+    pub fn set_overline(&mut self, overline: bool) {
+        self.effects.is_overline = overline;
+    }
+
+    /// Sets the underline style of this style.
+    ///
+    /// Note that this does not set the underline effect, see [`set_underline`][].
+    ///
+    /// [`set_underline`]: #method.set_underline
+    pub fn set_underline_style(&mut self, underline_style: UnderlineStyle) {
+        self.underline_style = Some(underline_style);
+    }
+
+    /// Sets the underline color of this style.
+    ///
+    /// Note that this does not set the underline effect, see [`set_underline`][].
+    ///
+    /// [`set_underline`]: #method.set_underline
+    pub fn set_underline_color(&mut self, color: Color) {
+        self.underline_color = Some(color);
+    }
+
+    /// Returns the underline style of this style if the underline effect is set.
+    pub fn get_underline_style(&self) -> Option<UnderlineStyle> {
+        if self.effects.is_underline {
+            Some(self.underline_style.unwrap_or(UnderlineStyle::Single))
+        } else {
+            None
+        }
+    }
+
     /// Sets or unsets the given effect for this style.
     pub fn set_effect(&mut self, effect: Effect, set: bool) {
         self.effects.set(effect, set);
     }
+
+    /// Converts the colors of this style to the given color depth.
+    ///
+    /// See [`Color::downgrade`][] for more information.
+    ///
+    /// [`Color::downgrade`]: enum.Color.html#method.downgrade
+    pub fn downgrade(mut self, depth: ColorDepth) -> Style {
+        self.fg = self.fg.and_then(|color| color.downgrade(depth));
+        self.bg = self.bg.and_then(|color| color.downgrade(depth));
+        self.underline_color = self
+            .underline_color
+            .and_then(|color| color.downgrade(depth));
+        self
+    }
 }
 
 impl From<Effect> for Style {
@@ -564,6 +1330,11 @@ impl Effects {
             Effect::Italic => self.is_italic = set,
             Effect::Underline => self.is_underline = set,
             Effect::Strikethrough => self.is_strikethrough = set,
+            Effect::Dim => self.is_dim = set,
+            Effect::Reverse => self.is_reverse = set,
+            Effect::Blink => self.is_blink = set,
+            Effect::Hidden => self.is_hidden = set,
+            Effect::Overline => self.is_overline = set,
         }
     }
 
@@ -574,6 +1345,11 @@ impl Effects {
             Effect::Italic => self.is_italic,
             Effect::Underline => self.is_underline,
             Effect::Strikethrough => self.is_strikethrough,
+            Effect::Dim => self.is_dim,
+            Effect::Reverse => self.is_reverse,
+            Effect::Blink => self.is_blink,
+            Effect::Hidden => self.is_hidden,
+            Effect::Overline => self.is_overline,
         }
     }
 
@@ -584,12 +1360,17 @@ impl Effects {
             is_italic: self.is_italic || other.is_italic,
             is_underline: self.is_underline || other.is_underline,
             is_strikethrough: self.is_strikethrough || other.is_strikethrough,
+            is_dim: self.is_dim || other.is_dim,
+            is_reverse: self.is_reverse || other.is_reverse,
+            is_blink: self.is_blink || other.is_blink,
+            is_hidden: self.is_hidden || other.is_hidden,
+            is_overline: self.is_overline || other.is_overline,
         }
     }
 
     /// Checks whether this set of text effects is empty.
     pub fn is_empty(&self) -> bool {
-        !self.is_bold && !self.is_italic && !self.is_underline && !self.is_strikethrough
+        EFFECTS.iter().all(|effect| !self.is_set(*effect))
     }
 }
 
@@ -642,6 +1423,47 @@ impl From<Effects> for EffectsIter {
     }
 }
 
+impl Color {
+    /// Converts this color to the given color depth.
+    ///
+    /// If the color can be displayed with the given color depth, it is returned unchanged.
+    /// Otherwise it is replaced with the closest color that is available in the target color
+    /// depth.  For [`ColorDepth::NoColor`][], this method always returns `None`.
+    ///
+    /// The ANSI colors are assumed to have the colors of the VGA palette.  The indices 0–15 of the
+    /// 256-color palette are mapped to the ANSI colors.  The distance between two colors is
+    /// measured using the *redmean* approximation of the perceptual color difference.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use text_style::{AnsiColor, Color, ColorDepth};
+    ///
+    /// let orange = Color::Rgb { r: 255, g: 135, b: 0 };
+    /// assert_eq!(orange.downgrade(ColorDepth::TrueColor), Some(orange));
+    /// assert_eq!(orange.downgrade(ColorDepth::Indexed), Some(Color::Indexed(208)));
+    /// assert_eq!(orange.downgrade(ColorDepth::Ansi), Some(AnsiColor::Red.light()));
+    /// assert_eq!(orange.downgrade(ColorDepth::NoColor), None);
+    /// ```
+    ///
+    /// [`ColorDepth::NoColor`]: enum.ColorDepth.html#variant.NoColor
+    pub fn downgrade(self, depth: ColorDepth) -> Option<Color> {
+        match (depth, self) {
+            (ColorDepth::NoColor, _) => None,
+            (ColorDepth::Ansi, Color::Ansi { .. }) => Some(self),
+            (ColorDepth::Ansi, Color::Indexed(index)) => Some(
+                palette::indexed_ansi(index)
+                    .unwrap_or_else(|| palette::nearest_ansi(palette::indexed_rgb(index))),
+            ),
+            (ColorDepth::Ansi, Color::Rgb { r, g, b }) => Some(palette::nearest_ansi((r, g, b))),
+            (ColorDepth::Indexed, Color::Rgb { r, g, b }) => {
+                Some(Color::Indexed(palette::nearest_indexed((r, g, b))))
+            }
+            (ColorDepth::Indexed, _) | (ColorDepth::TrueColor, _) => Some(self),
+        }
+    }
+}
+
 impl AnsiColor {
     /// Returns the dark variant of this ANSI color.
     pub fn dark(self) -> Color {
@@ -659,3 +1481,47 @@ impl AnsiColor {
         }
     }
 }
+
+impl<T> ConversionError<T> {
+    /// Returns the given value if the list of unsupported attributes is empty, or an error
+    /// containing the value and the unsupported attributes otherwise.
+    #[cfg(any(feature = "crossterm", feature = "cursive"))]
+    pub(crate) fn check(value: T, mut unsupported: Vec<String>) -> Result<T, ConversionError<T>> {
+        if unsupported.is_empty() {
+            Ok(value)
+        } else {
+            unsupported.sort();
+            unsupported.dedup();
+            Err(ConversionError { value, unsupported })
+        }
+    }
+
+    /// Returns the converted value and adds the unsupported attributes of this error to the given
+    /// list.
+    #[cfg(any(feature = "crossterm", feature = "cursive"))]
+    pub(crate) fn unwrap_into(result: Result<T, Self>, unsupported: &mut Vec<String>) -> T {
+        match result {
+            Ok(value) => value,
+            Err(err) => {
+                unsupported.extend(err.unsupported);
+                err.value
+            }
+        }
+    }
+
+    /// Applies the given function to the converted value.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> ConversionError<U> {
+        ConversionError {
+            value: f(self.value),
+            unsupported: self.unsupported,
+        }
+    }
+}
+
+impl<T> fmt::Display for ConversionError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unsupported attributes: {}", self.unsupported.join(", "))
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for ConversionError<T> {}
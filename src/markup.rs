@@ -0,0 +1,222 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+//! Parser for a simple inline markup language.
+//!
+//! This module provides the [`parse`][] function that converts a string with inline style tags
+//! into a [`StyledText`][].  A tag is enclosed in square brackets and contains a non-empty style
+//! specification, for example `[bold red on blue]`.  See [`Style`][] for a description of the
+//! format.
+//!
+//! The style of a tag is applied to the text until the next closing tag, `[/]`.  The closing tag
+//! may repeat the content of the opening tag, for example `[bold]test[/bold]`.  Tags can be
+//! nested:  the style of the inner tag is combined with the style of the outer tag using
+//! [`Style::and`][].  Tags that are not closed at the end of the string are closed implicitly.
+//!
+//! A backslash escapes an opening square bracket or another backslash.  Use the [`escape`][]
+//! function to escape text that is inserted into markup.
+//!
+//! # Example
+//!
+//! ```
+//! use text_style::{markup, AnsiColor, StyledString};
+//!
+//! let path = "src/lib.rs";
+//! let text = markup::parse(&format!(
+//!     "[bold red on blue]error[/]: [italic]{}[/]",
+//!     markup::escape(path),
+//! ))
+//! .expect("Failed to parse markup");
+//! assert_eq!(text.spans(), &[
+//!     StyledString::plain("error".to_owned())
+//!         .with(AnsiColor::Red.dark())
+//!         .on(AnsiColor::Blue.dark())
+//!         .bold(),
+//!     StyledString::plain(": ".to_owned()),
+//!     StyledString::plain(path.to_owned()).italic(),
+//! ]);
+//! ```
+//!
+//! [`Style`]: ../struct.Style.html#parsing-and-formatting
+//! [`StyledText`]: ../struct.StyledText.html
+//! [`Style::and`]: ../struct.Style.html#method.and
+//! [`escape`]: fn.escape.html
+//! [`parse`]: fn.parse.html
+
+use std::error;
+use std::fmt;
+
+use crate::{Style, StyledString, StyledText};
+
+/// An error that occured during parsing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Error {
+    /// The kind of the error.
+    pub kind: ErrorKind,
+    /// The byte offset of the invalid text in the input string.
+    pub offset: usize,
+    /// The invalid text, for example the tag or the style word.
+    pub text: String,
+}
+
+/// The kind of a parser [`Error`][].
+///
+/// [`Error`]: struct.Error.html
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ErrorKind {
+    /// A tag is not terminated with a closing square bracket.
+    UnterminatedTag,
+    /// A tag is empty or contains an unknown style word.
+    InvalidStyle,
+    /// A closing tag does not have a matching opening tag.
+    UnexpectedClosingTag,
+    /// The content of a closing tag does not match the content of the opening tag.
+    MismatchedClosingTag,
+}
+
+impl Error {
+    fn new(kind: ErrorKind, offset: usize, text: &str) -> Error {
+        Error {
+            kind,
+            offset,
+            text: text.to_owned(),
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self.kind {
+            ErrorKind::UnterminatedTag => "Unterminated tag",
+            ErrorKind::InvalidStyle => "Invalid style",
+            ErrorKind::UnexpectedClosingTag => "Unexpected closing tag",
+            ErrorKind::MismatchedClosingTag => "Mismatched closing tag",
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {:?} at offset {}",
+            self.description(),
+            self.text,
+            self.offset
+        )
+    }
+}
+
+impl error::Error for Error {}
+
+/// Parses a string with inline style tags into a styled text.
+///
+/// See the [module documentation](index.html) for a description of the syntax.
+///
+/// # Example
+///
+/// ```
+/// use text_style::{markup, StyledString};
+///
+/// let text = markup::parse("[bold]a [italic]b[/] \\[c][/]").expect("Failed to parse markup");
+/// assert_eq!(text.spans(), &[
+///     StyledString::plain("a ".to_owned()).bold(),
+///     StyledString::plain("b".to_owned()).bold().italic(),
+///     StyledString::plain(" [c]".to_owned()).bold(),
+/// ]);
+///
+/// let err = markup::parse("[bold]a[/] [boldd]b").unwrap_err();
+/// assert_eq!(err.kind, markup::ErrorKind::InvalidStyle);
+/// assert_eq!(err.offset, 12);
+/// assert_eq!(err.text, "boldd");
+/// ```
+pub fn parse(s: &str) -> Result<StyledText, Error> {
+    let mut text = StyledText::new();
+    let mut stack: Vec<(&str, Style)> = Vec::new();
+    let mut current = String::new();
+    let mut offset = 0;
+
+    while offset < s.len() {
+        let rest = &s[offset..];
+        let len = rest.find(&['[', '\\'][..]).unwrap_or(rest.len());
+        current.push_str(&rest[..len]);
+        offset += len;
+
+        let rest = &s[offset..];
+        if let Some(escaped) = rest.strip_prefix('\\') {
+            match escaped.chars().next() {
+                Some(c) if c == '[' || c == '\\' => {
+                    current.push(c);
+                    offset += 2;
+                }
+                _ => {
+                    current.push('\\');
+                    offset += 1;
+                }
+            }
+        } else if rest.starts_with('[') {
+            let end = rest
+                .find(']')
+                .ok_or_else(|| Error::new(ErrorKind::UnterminatedTag, offset, rest))?;
+            let tag = &rest[..=end];
+            let content = &rest[1..end];
+
+            push_span(
+                &mut text,
+                &mut current,
+                stack.last().map(|(_, style)| *style),
+            );
+            if let Some(name) = content.strip_prefix('/') {
+                let (open, _) = stack
+                    .pop()
+                    .ok_or_else(|| Error::new(ErrorKind::UnexpectedClosingTag, offset, tag))?;
+                let name = name.trim();
+                if !name.is_empty() && name != open.trim() {
+                    return Err(Error::new(ErrorKind::MismatchedClosingTag, offset, tag));
+                }
+            } else {
+                if content.trim().is_empty() {
+                    return Err(Error::new(ErrorKind::InvalidStyle, offset + 1, content));
+                }
+                let style = content.parse::<Style>().map_err(|err| {
+                    Error::new(ErrorKind::InvalidStyle, offset + 1 + err.offset, &err.text)
+                })?;
+                let style = match stack.last() {
+                    Some((_, parent)) => parent.and(style),
+                    None => style,
+                };
+                stack.push((content, style));
+            }
+            offset += tag.len();
+        }
+    }
+    push_span(
+        &mut text,
+        &mut current,
+        stack.last().map(|(_, style)| *style),
+    );
+
+    Ok(text)
+}
+
+/// Escapes the given text so that it can be inserted into markup.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(text_style::markup::escape("[a]\\b"), "\\[a]\\\\b");
+/// ```
+pub fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c == '[' || c == '\\' {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn push_span(text: &mut StyledText, s: &mut String, style: Option<Style>) {
+    if !s.is_empty() {
+        text.push(StyledString::new(std::mem::take(s), style));
+    }
+}
@@ -0,0 +1,235 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+//! RGB values for ANSI and indexed colors and color quantization.
+
+use crate::{AnsiColor, AnsiMode, Color, Palette, ANSI_COLORS};
+
+/// The levels of the color components in the 6×6×6 color cube of the 256-color palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Returns the RGB value of the given ANSI color in the VGA palette.
+pub fn ansi_rgb(color: AnsiColor, mode: AnsiMode) -> (u8, u8, u8) {
+    use AnsiColor::*;
+    use AnsiMode::*;
+
+    match (mode, color) {
+        (Dark, Black) => (0, 0, 0),
+        (Dark, Red) => (170, 0, 0),
+        (Dark, Green) => (0, 170, 0),
+        (Dark, Yellow) => (170, 85, 0),
+        (Dark, Blue) => (0, 0, 170),
+        (Dark, Magenta) => (170, 0, 170),
+        (Dark, Cyan) => (0, 170, 170),
+        (Dark, White) => (170, 170, 170),
+        (Light, Black) => (85, 85, 85),
+        (Light, Red) => (255, 85, 85),
+        (Light, Green) => (85, 255, 85),
+        (Light, Yellow) => (255, 255, 85),
+        (Light, Blue) => (85, 85, 255),
+        (Light, Magenta) => (255, 85, 255),
+        (Light, Cyan) => (85, 255, 255),
+        (Light, White) => (255, 255, 255),
+    }
+}
+
+/// Returns the ANSI color for the indices 0–15 of the 256-color palette.
+pub fn indexed_ansi(index: u8) -> Option<Color> {
+    match index {
+        0..=7 => Some(ANSI_COLORS[usize::from(index)].dark()),
+        8..=15 => Some(ANSI_COLORS[usize::from(index - 8)].light()),
+        _ => None,
+    }
+}
+
+/// Returns the RGB value of the given color from the 256-color palette, using the VGA palette for
+/// the ANSI colors.
+pub fn indexed_rgb(index: u8) -> (u8, u8, u8) {
+    match index {
+        0..=15 => {
+            let mode = if index < 8 {
+                AnsiMode::Dark
+            } else {
+                AnsiMode::Light
+            };
+            ansi_rgb(ANSI_COLORS[usize::from(index % 8)], mode)
+        }
+        16..=231 => {
+            let index = index - 16;
+            (
+                CUBE_LEVELS[usize::from(index / 36)],
+                CUBE_LEVELS[usize::from(index / 6 % 6)],
+                CUBE_LEVELS[usize::from(index % 6)],
+            )
+        }
+        _ => {
+            let level = 8 + 10 * (index - 232);
+            (level, level, level)
+        }
+    }
+}
+
+/// Returns the RGB value of the given color, using the VGA palette for the ANSI colors.
+pub fn rgb(color: Color) -> (u8, u8, u8) {
+    match color {
+        Color::Ansi { color, mode } => ansi_rgb(color, mode),
+        Color::Indexed(index) => indexed_rgb(index),
+        Color::Rgb { r, g, b } => (r, g, b),
+    }
+}
+
+/// Returns the entry of the 256-color palette that is closest to the given RGB color.
+///
+/// Only the color cube and the grayscale ramp (indices 16–255) are considered as the ANSI colors
+/// depend on the terminal configuration.
+pub fn nearest_indexed(color: (u8, u8, u8)) -> u8 {
+    let (r, g, b) = color;
+    let cube_index = |v: u8| match v {
+        0..=47 => 0,
+        48..=114 => 1,
+        _ => (v - 35) / 40,
+    };
+    let (ri, gi, bi) = (cube_index(r), cube_index(g), cube_index(b));
+    let cube = 16 + 36 * ri + 6 * gi + bi;
+
+    let average = (u16::from(r) + u16::from(g) + u16::from(b)) / 3;
+    let gray = if average < 8 {
+        232
+    } else {
+        232 + std::cmp::min((average - 3) / 10, 23) as u8
+    };
+
+    if distance(color, indexed_rgb(gray)) < distance(color, indexed_rgb(cube)) {
+        gray
+    } else {
+        cube
+    }
+}
+
+/// Returns the ANSI color that is closest to the given RGB color in the VGA palette.
+pub fn nearest_ansi(color: (u8, u8, u8)) -> Color {
+    (0..16)
+        .map(|index| indexed_ansi(index).expect("Index is not an ANSI color"))
+        .min_by_key(|ansi| distance(color, rgb(*ansi)))
+        .expect("ANSI color list is empty")
+}
+
+/// Calculates the perceptual distance between two colors.
+///
+/// This function uses the *redmean* approximation, a weighted Euclidean distance that depends on
+/// the mean of the red components.  It returns the squared distance.
+fn distance(c1: (u8, u8, u8), c2: (u8, u8, u8)) -> u32 {
+    let rmean = (u32::from(c1.0) + u32::from(c2.0)) / 2;
+    let dr = i32::from(c1.0) - i32::from(c2.0);
+    let dg = i32::from(c1.1) - i32::from(c2.1);
+    let db = i32::from(c1.2) - i32::from(c2.2);
+    let (dr, dg, db) = ((dr * dr) as u32, (dg * dg) as u32, (db * db) as u32);
+    (((512 + rmean) * dr) >> 8) + 4 * dg + (((767 - rmean) * db) >> 8)
+}
+
+impl Palette {
+    /// Returns the VGA palette.
+    pub fn vga() -> Palette {
+        Palette::from_fn(ansi_rgb)
+    }
+
+    /// Returns the default palette of xterm.
+    pub fn xterm() -> Palette {
+        Palette {
+            dark: [
+                (0, 0, 0),
+                (205, 0, 0),
+                (0, 205, 0),
+                (205, 205, 0),
+                (0, 0, 238),
+                (205, 0, 205),
+                (0, 205, 205),
+                (229, 229, 229),
+            ],
+            light: [
+                (127, 127, 127),
+                (255, 0, 0),
+                (0, 255, 0),
+                (255, 255, 0),
+                (92, 92, 255),
+                (255, 0, 255),
+                (0, 255, 255),
+                (255, 255, 255),
+            ],
+        }
+    }
+
+    fn from_fn(f: impl Fn(AnsiColor, AnsiMode) -> (u8, u8, u8)) -> Palette {
+        let mut palette = Palette {
+            dark: [(0, 0, 0); 8],
+            light: [(0, 0, 0); 8],
+        };
+        for (i, color) in ANSI_COLORS.iter().enumerate() {
+            palette.dark[i] = f(*color, AnsiMode::Dark);
+            palette.light[i] = f(*color, AnsiMode::Light);
+        }
+        palette
+    }
+
+    /// Returns the RGB value of the given ANSI color.
+    pub fn get(&self, color: AnsiColor, mode: AnsiMode) -> (u8, u8, u8) {
+        let index = usize::from(color.index());
+        match mode {
+            AnsiMode::Dark => self.dark[index],
+            AnsiMode::Light => self.light[index],
+        }
+    }
+
+    /// Sets the RGB value of the given ANSI color.
+    pub fn set(&mut self, color: AnsiColor, mode: AnsiMode, rgb: (u8, u8, u8)) {
+        let index = usize::from(color.index());
+        match mode {
+            AnsiMode::Dark => self.dark[index] = rgb,
+            AnsiMode::Light => self.light[index] = rgb,
+        }
+    }
+
+    /// Returns the RGB value of the given color, using this palette for the ANSI colors and the
+    /// indices 0–15 of the 256-color palette.
+    pub fn rgb(&self, color: Color) -> (u8, u8, u8) {
+        match color {
+            Color::Ansi { color, mode } => self.get(color, mode),
+            Color::Indexed(index) => match indexed_ansi(index) {
+                Some(color) => self.rgb(color),
+                None => indexed_rgb(index),
+            },
+            Color::Rgb { r, g, b } => (r, g, b),
+        }
+    }
+
+    /// Returns the RGB value of the given color as a hex string, for example `#ff0000`.
+    pub(crate) fn hex(&self, color: Color) -> String {
+        let (r, g, b) = self.rgb(color);
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Palette {
+        Palette::vga()
+    }
+}
+
+impl AnsiColor {
+    /// Returns the position of this color in [`ANSI_COLORS`][], i. e. its index in the
+    /// 256-color palette for the dark mode and its offset in the SGR color parameters.
+    ///
+    /// [`ANSI_COLORS`]: ../constant.ANSI_COLORS.html
+    pub(crate) fn index(self) -> u8 {
+        match self {
+            AnsiColor::Black => 0,
+            AnsiColor::Red => 1,
+            AnsiColor::Green => 2,
+            AnsiColor::Yellow => 3,
+            AnsiColor::Blue => 4,
+            AnsiColor::Magenta => 5,
+            AnsiColor::Cyan => 6,
+            AnsiColor::White => 7,
+        }
+    }
+}
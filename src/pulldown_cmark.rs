@@ -0,0 +1,544 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+//! Conversion of Markdown to styled text using [`pulldown-cmark`][].
+//!
+//! *Requires the `pulldown-cmark` feature.*
+//!
+//! This module provides the [`parse`][] function and the [`Parser`][] struct that convert Markdown
+//! into a [`StyledText`][] that can be rendered with any backend.  The style for each Markdown
+//! element is configured using the [`Styles`][] struct.
+//!
+//! The text is split into lines, and blocks are separated by an empty line.  Block quotes are
+//! prefixed with `> `, list items with a bullet or their number, and code blocks are indented.
+//! Nested blocks are indented accordingly.  Links are stored in the [`link`][`StyledStr::link`]
+//! field of the styled strings.  Soft line breaks are converted to spaces, so the text should be
+//! wrapped before it is displayed.  HTML is ignored.
+//!
+//! Fenced code blocks can be highlighted with a custom function, see [`Parser::highlighter`][],
+//! or with `syntect` if the `syntect-parsing` feature is enabled, see [`Parser::syntect`][].
+//!
+//! # Example
+//!
+//! ```
+//! let text = text_style::pulldown_cmark::parse("# Usage\n\nRun `make` *now*.");
+//! text_style::ansi::render_iter(std::io::stdout(), text.iter())
+//!     .expect("Failed to render text");
+//! ```
+//!
+//! [`pulldown-cmark`]: https://docs.rs/pulldown-cmark
+//! [`Parser`]: struct.Parser.html
+//! [`Parser::highlighter`]: struct.Parser.html#method.highlighter
+//! [`Parser::syntect`]: struct.Parser.html#method.syntect
+//! [`StyledStr::link`]: ../struct.StyledStr.html#structfield.link
+//! [`StyledText`]: ../struct.StyledText.html
+//! [`Styles`]: struct.Styles.html
+//! [`parse`]: fn.parse.html
+
+use std::fmt;
+
+use pulldown_cmark::{CodeBlockKind, Event, Tag};
+#[cfg(feature = "syntect-parsing")]
+use syntect::{highlighting, parsing};
+
+#[cfg(feature = "syntect-parsing")]
+use crate::syntect::HighlightedLines;
+use crate::{AnsiColor, Effect, Hyperlink, Style, StyledString, StyledText};
+
+/// The styles for the Markdown elements.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Styles {
+    /// The styles for the headings of the levels 1 to 6.
+    pub headings: [Style; 6],
+    /// The style for emphasized text.
+    pub emphasis: Style,
+    /// The style for strong text.
+    pub strong: Style,
+    /// The style for strikethrough text.
+    pub strikethrough: Style,
+    /// The style for inline code.
+    pub code: Style,
+    /// The style for code blocks that are not highlighted.
+    pub code_block: Style,
+    /// The style for links.
+    pub link: Style,
+    /// The style for block quotes.
+    pub block_quote: Style,
+    /// The style for the bullets and numbers of list items.
+    pub list_marker: Style,
+    /// The style for horizontal rules.
+    pub rule: Style,
+}
+
+impl Default for Styles {
+    fn default() -> Styles {
+        let heading = Style::effect(Effect::Bold);
+        Styles {
+            headings: [
+                heading.and(Style::effect(Effect::Underline)),
+                heading,
+                heading,
+                heading,
+                heading,
+                heading,
+            ],
+            emphasis: Style::effect(Effect::Italic),
+            strong: Style::effect(Effect::Bold),
+            strikethrough: Style::effect(Effect::Strikethrough),
+            code: Style::fg(AnsiColor::Yellow.dark()),
+            code_block: Style::fg(AnsiColor::Yellow.dark()),
+            link: Style::fg(AnsiColor::Blue.light()).and(Style::effect(Effect::Underline)),
+            block_quote: Style::effect(Effect::Italic),
+            list_marker: Style::effect(Effect::Bold),
+            rule: Style::effect(Effect::Dim),
+        }
+    }
+}
+
+type Highlighter<'a> = Box<dyn FnMut(&str, &str) -> Option<StyledText> + 'a>;
+
+/// A configurable Markdown parser.
+///
+/// See the [module documentation](index.html) for more information.
+#[derive(Default)]
+pub struct Parser<'a> {
+    styles: Styles,
+    highlighter: Option<Highlighter<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    /// Creates a new parser with the default styles and without a highlighter.
+    pub fn new() -> Parser<'a> {
+        Default::default()
+    }
+
+    /// Sets the styles for the Markdown elements.
+    pub fn styles(mut self, styles: Styles) -> Self {
+        self.styles = styles;
+        self
+    }
+
+    /// Sets the highlighter for fenced code blocks.
+    ///
+    /// The highlighter is called with the language of the code block (the first word of the info
+    /// string) and the code.  If it returns `None`, the code is styled with the `code_block`
+    /// style.
+    ///
+    /// # Example
+    ///
+    /// Highlighting code blocks with `syntect` (requires the `syntect` feature and `syntect`’s
+    /// `parsing` feature, see also [`syntect`][]):
+    ///
+    #[cfg_attr(feature = "syntect", doc = "```")]
+    #[cfg_attr(not(feature = "syntect"), doc = "```ignore")]
+    /// use syntect::{easy, highlighting, parsing, util};
+    /// use text_style::{pulldown_cmark::Parser, StyledStr, StyledText};
+    ///
+    /// let ps = parsing::SyntaxSet::load_defaults_newlines();
+    /// let ts = highlighting::ThemeSet::load_defaults();
+    /// let theme = &ts.themes["base16-ocean.dark"];
+    ///
+    /// let mut parser = Parser::new().highlighter(|lang, code| {
+    ///     let syntax = ps.find_syntax_by_token(lang)?;
+    ///     let mut h = easy::HighlightLines::new(syntax, theme);
+    ///     let mut text = StyledText::new();
+    ///     for line in util::LinesWithEndings::from(code) {
+    ///         for range in h.highlight(line, &ps) {
+    ///             text.push(StyledStr::from(&range));
+    ///         }
+    ///     }
+    ///     Some(text)
+    /// });
+    /// let text = parser.parse("```rust\nfn main() {}\n```");
+    /// assert!(text.len() > 1);
+    /// ```
+    ///
+    /// [`syntect`]: #method.syntect
+    pub fn highlighter(
+        mut self,
+        highlighter: impl FnMut(&str, &str) -> Option<StyledText> + 'a,
+    ) -> Self {
+        self.highlighter = Some(Box::new(highlighter));
+        self
+    }
+
+    /// Sets a highlighter for fenced code blocks that uses [`syntect`][].
+    ///
+    /// *Requires the `syntect-parsing` feature.*
+    ///
+    /// The syntax of a code block is selected using [`SyntaxSet::find_syntax_by_token`][] with the
+    /// language of the code block.  If there is no matching syntax, the code is styled with the
+    /// `code_block` style.  The highlighted code is converted using the given converter, see
+    /// [`HighlightedLines`][].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use syntect::{highlighting, parsing};
+    /// use text_style::{pulldown_cmark::Parser, syntect::Converter};
+    ///
+    /// let ps = parsing::SyntaxSet::load_defaults_newlines();
+    /// let ts = highlighting::ThemeSet::load_defaults();
+    /// let theme = &ts.themes["base16-ocean.dark"];
+    ///
+    /// let converter = Converter::new().omit_theme_background(theme);
+    /// let mut parser = Parser::new().syntect(&ps, theme, converter);
+    /// let text = parser.parse("```rust\nfn main() {}\n```");
+    /// assert!(text.len() > 1);
+    /// ```
+    ///
+    /// [`syntect`]: https://docs.rs/syntect
+    /// [`SyntaxSet::find_syntax_by_token`]: https://docs.rs/syntect/latest/syntect/parsing/struct.SyntaxSet.html#method.find_syntax_by_token
+    /// [`HighlightedLines`]: ../syntect/struct.HighlightedLines.html
+    #[cfg(feature = "syntect-parsing")]
+    pub fn syntect(
+        self,
+        syntax_set: &'a parsing::SyntaxSet,
+        theme: &'a highlighting::Theme,
+        converter: crate::syntect::Converter,
+    ) -> Self {
+        self.highlighter(move |lang, code| {
+            let syntax = syntax_set.find_syntax_by_token(lang)?;
+            let lines = HighlightedLines::new(syntax_set, syntax, theme, code)
+                .converter(converter)
+                .merge(true);
+            Some(lines.flatten().collect())
+        })
+    }
+
+    /// Parses the given Markdown string with the strikethrough and task list extensions.
+    pub fn parse(&mut self, s: &str) -> StyledText {
+        let mut options = pulldown_cmark::Options::empty();
+        options.insert(pulldown_cmark::Options::ENABLE_STRIKETHROUGH);
+        options.insert(pulldown_cmark::Options::ENABLE_TASKLISTS);
+        self.parse_events(pulldown_cmark::Parser::new_ext(s, options))
+    }
+
+    /// Converts the given Markdown events into styled text.
+    ///
+    /// Tables are rendered as lines with the cells separated by `|`.
+    pub fn parse_events<'e>(&mut self, events: impl IntoIterator<Item = Event<'e>>) -> StyledText {
+        let mut converter = Converter::new(self);
+        for event in events {
+            converter.handle(event);
+        }
+        converter.ensure_line_end();
+        converter.text
+    }
+}
+
+impl<'a> fmt::Debug for Parser<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Parser")
+            .field("styles", &self.styles)
+            .field("highlighter", &self.highlighter.as_ref().map(|_| "..."))
+            .finish()
+    }
+}
+
+/// The state of a single conversion.
+struct Converter<'p, 'a> {
+    parser: &'p mut Parser<'a>,
+    text: StyledText,
+    styles: Vec<Style>,
+    link: Option<Hyperlink<'static>>,
+    prefixes: Vec<(String, Style)>,
+    lists: Vec<Option<u64>>,
+    code_block: Option<(String, String)>,
+    is_line_start: bool,
+    is_item_start: bool,
+    needs_blank_line: bool,
+}
+
+impl<'p, 'a> Converter<'p, 'a> {
+    fn new(parser: &'p mut Parser<'a>) -> Self {
+        Converter {
+            parser,
+            text: StyledText::new(),
+            styles: Vec::new(),
+            link: None,
+            prefixes: Vec::new(),
+            lists: Vec::new(),
+            code_block: None,
+            is_line_start: true,
+            is_item_start: false,
+            needs_blank_line: false,
+        }
+    }
+
+    fn handle(&mut self, event: Event<'_>) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(s) => {
+                if let Some((_, code)) = &mut self.code_block {
+                    code.push_str(&s);
+                } else {
+                    self.write(&s, self.style());
+                }
+            }
+            Event::Code(s) => self.write(&s, self.style().and(self.parser.styles.code)),
+            Event::Html(_) => {}
+            Event::FootnoteReference(label) => self.write(&format!("[{}]", label), self.style()),
+            Event::SoftBreak => self.write(" ", self.style()),
+            Event::HardBreak => self.write("\n", self.style()),
+            Event::Rule => {
+                self.start_block();
+                self.write("---", self.parser.styles.rule);
+                self.end_block();
+            }
+            Event::TaskListMarker(checked) => {
+                let marker = if checked { "[x] " } else { "[ ] " };
+                self.write(marker, self.parser.styles.list_marker);
+            }
+        }
+    }
+
+    fn start(&mut self, tag: Tag<'_>) {
+        match tag {
+            Tag::Paragraph | Tag::Table(_) => self.start_block(),
+            Tag::Heading(level) => {
+                self.start_block();
+                let index = (level as usize).saturating_sub(1).min(5);
+                self.push_style(self.parser.styles.headings[index]);
+            }
+            Tag::BlockQuote => {
+                self.start_block();
+                let style = self.parser.styles.block_quote;
+                self.prefixes.push(("> ".to_owned(), style));
+                self.push_style(style);
+            }
+            Tag::CodeBlock(kind) => {
+                self.start_block();
+                let lang = match kind {
+                    CodeBlockKind::Fenced(info) => info
+                        .split(|c: char| c == ',' || c.is_whitespace())
+                        .next()
+                        .unwrap_or_default()
+                        .to_owned(),
+                    CodeBlockKind::Indented => String::new(),
+                };
+                self.code_block = Some((lang, String::new()));
+            }
+            Tag::List(start) => {
+                if self.lists.is_empty() {
+                    self.start_block();
+                } else {
+                    self.is_item_start = false;
+                    self.ensure_line_end();
+                }
+                self.lists.push(start);
+            }
+            Tag::Item => {
+                self.ensure_line_end();
+                if self.needs_blank_line {
+                    self.write_blank_line();
+                }
+                let marker = match self.lists.last_mut() {
+                    Some(Some(n)) => {
+                        *n += 1;
+                        format!("{}. ", *n - 1)
+                    }
+                    _ => "• ".to_owned(),
+                };
+                self.write(&marker, self.parser.styles.list_marker);
+                self.prefixes
+                    .push((" ".repeat(marker.chars().count()), Style::default()));
+                self.is_item_start = true;
+            }
+            Tag::FootnoteDefinition(label) => {
+                self.start_block();
+                self.write(&format!("[{}]: ", label), self.style());
+            }
+            Tag::TableHead | Tag::TableRow => {}
+            Tag::TableCell => {
+                if !self.is_line_start {
+                    self.write(" | ", self.style());
+                }
+            }
+            Tag::Emphasis => self.push_style(self.parser.styles.emphasis),
+            Tag::Strong => self.push_style(self.parser.styles.strong),
+            Tag::Strikethrough => self.push_style(self.parser.styles.strikethrough),
+            Tag::Link(_, url, _) => {
+                self.push_style(self.parser.styles.link);
+                self.link = Some(Hyperlink::from(url.into_string()));
+            }
+            Tag::Image(..) => {}
+        }
+    }
+
+    fn end(&mut self, tag: Tag<'_>) {
+        match tag {
+            Tag::Paragraph | Tag::Table(_) | Tag::FootnoteDefinition(_) => self.end_block(),
+            Tag::Heading(_) => {
+                self.styles.pop();
+                self.end_block();
+            }
+            Tag::BlockQuote => {
+                self.ensure_line_end();
+                self.prefixes.pop();
+                self.styles.pop();
+                self.needs_blank_line = true;
+            }
+            Tag::CodeBlock(_) => {
+                if let Some((lang, code)) = self.code_block.take() {
+                    let highlighted = if lang.is_empty() {
+                        None
+                    } else {
+                        self.parser
+                            .highlighter
+                            .as_mut()
+                            .and_then(|highlighter| highlighter(&lang, &code))
+                    };
+                    self.prefixes.push(("    ".to_owned(), Style::default()));
+                    match highlighted {
+                        Some(text) => {
+                            for s in text {
+                                self.write(&s.s, s.style.unwrap_or_default());
+                            }
+                        }
+                        None => self.write(&code, self.parser.styles.code_block),
+                    }
+                    self.ensure_line_end();
+                    self.prefixes.pop();
+                }
+                self.needs_blank_line = true;
+            }
+            Tag::List(_) => {
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.end_block();
+                }
+            }
+            Tag::Item => {
+                self.ensure_line_end();
+                self.prefixes.pop();
+                self.is_item_start = false;
+            }
+            Tag::TableHead | Tag::TableRow => self.ensure_line_end(),
+            Tag::TableCell | Tag::Image(..) => {}
+            Tag::Emphasis | Tag::Strong | Tag::Strikethrough => {
+                self.styles.pop();
+            }
+            Tag::Link(..) => {
+                self.styles.pop();
+                self.link = None;
+            }
+        }
+    }
+
+    fn style(&self) -> Style {
+        self.styles.last().copied().unwrap_or_default()
+    }
+
+    fn push_style(&mut self, style: Style) {
+        self.styles.push(self.style().and(style));
+    }
+
+    fn start_block(&mut self) {
+        if self.is_item_start {
+            // the first block of a list item is placed next to the list marker
+            self.is_item_start = false;
+        } else {
+            self.ensure_line_end();
+            if self.needs_blank_line {
+                self.write_blank_line();
+            }
+        }
+        self.needs_blank_line = false;
+    }
+
+    fn end_block(&mut self) {
+        self.ensure_line_end();
+        self.needs_blank_line = true;
+    }
+
+    fn write(&mut self, s: &str, style: Style) {
+        self.is_item_start = false;
+        for (i, line) in s.split('\n').enumerate() {
+            if i > 0 {
+                self.end_line();
+            }
+            if !line.is_empty() {
+                if self.is_line_start {
+                    for (prefix, style) in self.prefixes.clone() {
+                        self.push(&prefix, style, None);
+                    }
+                    self.is_line_start = false;
+                }
+                self.push(line, style, self.link.clone());
+            }
+        }
+    }
+
+    fn write_blank_line(&mut self) {
+        // only write the prefixes up to the last non-whitespace character
+        let len = self
+            .prefixes
+            .iter()
+            .rposition(|(prefix, _)| !prefix.trim().is_empty())
+            .map(|idx| idx + 1)
+            .unwrap_or_default();
+        for (i, (prefix, style)) in self.prefixes.clone().into_iter().enumerate().take(len) {
+            let prefix = if i + 1 == len {
+                prefix.trim_end()
+            } else {
+                &prefix
+            };
+            self.push(prefix, style, None);
+        }
+        self.end_line();
+        self.needs_blank_line = false;
+    }
+
+    fn ensure_line_end(&mut self) {
+        if !self.is_line_start {
+            self.end_line();
+        }
+    }
+
+    fn end_line(&mut self) {
+        self.push("\n", Style::default(), None);
+        self.is_line_start = true;
+    }
+
+    fn push(&mut self, s: &str, style: Style, link: Option<Hyperlink<'static>>) {
+        let style = if style == Style::default() {
+            None
+        } else {
+            Some(style)
+        };
+        match self.text.spans_mut().last_mut() {
+            Some(last) if last.style == style && last.link == link && !last.s.ends_with('\n') => {
+                last.s.push_str(s);
+            }
+            _ => {
+                let mut span = StyledString::new(s.to_owned(), style);
+                span.link = link;
+                self.text.push(span);
+            }
+        }
+    }
+}
+
+/// Converts the given Markdown string into styled text using the default styles.
+///
+/// See the [module documentation](index.html) for more information.
+///
+/// # Example
+///
+/// ```
+/// use text_style::StyledString;
+///
+/// let text = text_style::pulldown_cmark::parse("- *a*\n- b");
+/// assert_eq!(text.spans(), &[
+///     StyledString::plain("• ".to_owned()).bold(),
+///     StyledString::plain("a".to_owned()).italic(),
+///     StyledString::plain("\n".to_owned()),
+///     StyledString::plain("• ".to_owned()).bold(),
+///     StyledString::plain("b\n".to_owned()),
+/// ]);
+/// ```
+pub fn parse(s: &str) -> StyledText {
+    Parser::new().parse(s)
+}
@@ -0,0 +1,235 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+//! Parsing and formatting of styles and colors using a textual specification.
+
+use std::fmt;
+use std::str;
+
+use crate::{
+    AnsiColor, AnsiMode, Color, Effect, ParseStyleError, Style, UnderlineStyle, ANSI_COLORS,
+};
+
+const ANSI_COLOR_NAMES: &[&str] = &[
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+const UNDERLINE_STYLE_NAMES: &[(UnderlineStyle, &str)] = &[
+    (UnderlineStyle::Double, "double-underline"),
+    (UnderlineStyle::Curly, "curly-underline"),
+    (UnderlineStyle::Dotted, "dotted-underline"),
+    (UnderlineStyle::Dashed, "dashed-underline"),
+];
+
+impl str::FromStr for Style {
+    type Err = ParseStyleError;
+
+    fn from_str(s: &str) -> Result<Style, ParseStyleError> {
+        let mut style = Style::default();
+        let mut words = split_words(s).into_iter();
+        while let Some((offset, word)) = words.next() {
+            let color_arg = |words: &mut dyn Iterator<Item = (usize, &str)>| {
+                let (offset, color) = words
+                    .next()
+                    .ok_or_else(|| ParseStyleError::new(offset, word))?;
+                parse_color(color).ok_or_else(|| ParseStyleError::new(offset, color))
+            };
+
+            if word == "on" {
+                style.bg = Some(color_arg(&mut words)?);
+            } else if word == "underline-color" {
+                style.underline_color = Some(color_arg(&mut words)?);
+            } else if let Some(effect) = parse_effect(word) {
+                style.effects.set(effect, true);
+            } else if let Some(underline_style) = parse_underline_style(word) {
+                style.set_underline_style(underline_style);
+                style.effects.set(Effect::Underline, true);
+            } else {
+                style.fg =
+                    Some(parse_color(word).ok_or_else(|| ParseStyleError::new(offset, word))?);
+            }
+        }
+        Ok(style)
+    }
+}
+
+impl fmt::Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut words: Vec<String> = Vec::new();
+        for effect in self.effects {
+            let underline_style = self
+                .get_underline_style()
+                .and_then(get_underline_style_name);
+            match (effect, underline_style) {
+                (Effect::Underline, Some(name)) => words.push(name.to_owned()),
+                _ => words.push(get_effect_name(effect).to_owned()),
+            }
+        }
+        if let Some(fg) = self.fg {
+            words.push(fg.to_string());
+        }
+        if let Some(bg) = self.bg {
+            words.push(format!("on {}", bg));
+        }
+        if let Some(color) = self.underline_color {
+            words.push(format!("underline-color {}", color));
+        }
+        f.write_str(&words.join(" "))
+    }
+}
+
+impl str::FromStr for Color {
+    type Err = ParseStyleError;
+
+    fn from_str(s: &str) -> Result<Color, ParseStyleError> {
+        let trimmed = s.trim_start();
+        let offset = s.len() - trimmed.len();
+        let trimmed = trimmed.trim_end();
+        parse_color(trimmed).ok_or_else(|| ParseStyleError::new(offset, trimmed))
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Color::Ansi { color, mode } => {
+                if *mode == AnsiMode::Light {
+                    f.write_str("bright-")?;
+                }
+                f.write_str(ANSI_COLOR_NAMES[usize::from(color.index())])
+            }
+            Color::Indexed(index) => write!(f, "{}", index),
+            Color::Rgb { r, g, b } => write!(f, "#{:02x}{:02x}{:02x}", r, g, b),
+        }
+    }
+}
+
+impl ParseStyleError {
+    fn new(offset: usize, text: &str) -> ParseStyleError {
+        ParseStyleError {
+            offset,
+            text: text.to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for ParseStyleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid style specification {:?} at offset {}",
+            self.text, self.offset
+        )
+    }
+}
+
+impl std::error::Error for ParseStyleError {}
+
+/// Splits the given string into whitespace-separated words and returns the words and their
+/// offsets.  Whitespace within parentheses does not separate words.
+fn split_words(s: &str) -> Vec<(usize, &str)> {
+    let mut words = Vec::new();
+    let mut start = None;
+    let mut depth = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth > 0 => depth -= 1,
+            _ => {}
+        }
+        if c.is_whitespace() && depth == 0 {
+            if let Some(start) = start.take() {
+                words.push((start, &s[start..i]));
+            }
+        } else if start.is_none() {
+            start = Some(i);
+        }
+    }
+    if let Some(start) = start {
+        words.push((start, &s[start..]));
+    }
+    words
+}
+
+fn parse_effect(s: &str) -> Option<Effect> {
+    match s {
+        "bold" => Some(Effect::Bold),
+        "italic" => Some(Effect::Italic),
+        "underline" | "ul" => Some(Effect::Underline),
+        "strikethrough" | "strike" => Some(Effect::Strikethrough),
+        "dim" => Some(Effect::Dim),
+        "reverse" => Some(Effect::Reverse),
+        "blink" => Some(Effect::Blink),
+        "hidden" => Some(Effect::Hidden),
+        "overline" => Some(Effect::Overline),
+        _ => None,
+    }
+}
+
+pub(crate) fn get_effect_name(effect: Effect) -> &'static str {
+    match effect {
+        Effect::Bold => "bold",
+        Effect::Italic => "italic",
+        Effect::Underline => "underline",
+        Effect::Strikethrough => "strikethrough",
+        Effect::Dim => "dim",
+        Effect::Reverse => "reverse",
+        Effect::Blink => "blink",
+        Effect::Hidden => "hidden",
+        Effect::Overline => "overline",
+    }
+}
+
+/// Returns the name of the given underline style, or `None` for the single underline.
+pub(crate) fn get_underline_style_name(style: UnderlineStyle) -> Option<&'static str> {
+    UNDERLINE_STYLE_NAMES
+        .iter()
+        .find(|(s, _)| *s == style)
+        .map(|(_, name)| *name)
+}
+
+fn parse_underline_style(s: &str) -> Option<UnderlineStyle> {
+    UNDERLINE_STYLE_NAMES
+        .iter()
+        .find(|(_, name)| *name == s)
+        .map(|(style, _)| *style)
+}
+
+fn parse_color(s: &str) -> Option<Color> {
+    if let Some(hex) = s.strip_prefix('#') {
+        if hex.len() != 6 || !hex.is_ascii() {
+            return None;
+        }
+        let component = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        Some(Color::Rgb {
+            r: component(0)?,
+            g: component(2)?,
+            b: component(4)?,
+        })
+    } else if let Some(args) = s.strip_prefix("rgb(").and_then(|s| s.strip_suffix(')')) {
+        let mut args = args.split(',').map(|arg| arg.trim().parse::<u8>().ok());
+        let color = Color::Rgb {
+            r: args.next()??,
+            g: args.next()??,
+            b: args.next()??,
+        };
+        if args.next().is_some() {
+            None
+        } else {
+            Some(color)
+        }
+    } else if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) {
+        s.parse().ok().map(Color::Indexed)
+    } else if let Some(name) = s.strip_prefix("bright-") {
+        parse_ansi_color(name).map(AnsiColor::light)
+    } else {
+        parse_ansi_color(s).map(AnsiColor::dark)
+    }
+}
+
+fn parse_ansi_color(s: &str) -> Option<AnsiColor> {
+    ANSI_COLOR_NAMES
+        .iter()
+        .position(|name| *name == s)
+        .map(|i| ANSI_COLORS[i])
+}
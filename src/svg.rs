@@ -0,0 +1,359 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+//! Renderer for SVG images of styled text.
+//!
+//! This module provides the [`render`][] and [`render_iter`][] functions that write styled
+//! strings as an SVG document that looks like the output in a terminal.  The text is laid out on a
+//! grid of monospace character cells:  newlines start a new row, and every character occupies as
+//! many cells as its display width, see the [`width`][] module.  Tabs are expanded to the next
+//! tab stop, with a tab stop every eight columns.  Other control characters are removed, and a
+//! trailing newline does not add an empty row.
+//!
+//! Each row is written as a `<text>` element, and each styled string as a `<tspan>` element that
+//! is positioned at the column of its first character.  Background colors are drawn as `<rect>`
+//! elements behind the text.  Bold, italic, underline, strikethrough, overline, dim and hidden are
+//! converted to the corresponding SVG attributes.  The reverse effect swaps the foreground and the
+//! background color, and the blink effect as well as underline styles and colors are ignored.
+//! Hyperlinks are rendered as `<a>` elements if they are relative or use the `http`, `https` or
+//! `mailto` scheme.
+//!
+//! The [`Options`][] struct controls the font, the size of the cells, the default colors and the
+//! [`Palette`][] that is used to convert ANSI colors to RGB values.
+//!
+//! # Example
+//!
+//! ```
+//! use text_style::{svg, AnsiColor, StyledStr};
+//!
+//! let v = vec![
+//!     StyledStr::plain("error").with(AnsiColor::Red.light()).bold(),
+//!     StyledStr::plain(": file not found\n"),
+//! ];
+//! let mut w = Vec::new();
+//! svg::render_iter(&mut w, v.iter()).expect("Failed to render image");
+//! let svg = String::from_utf8(w).unwrap();
+//! assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+//! assert!(svg.contains("<tspan x=\"10\" fill=\"#ff5555\" font-weight=\"bold\">error</tspan>"));
+//! ```
+//!
+//! [`Options`]: struct.Options.html
+//! [`Palette`]: ../struct.Palette.html
+//! [`render`]: fn.render.html
+//! [`render_iter`]: fn.render_iter.html
+//! [`width`]: ../width/index.html
+
+use std::io;
+use std::iter;
+
+use crate::{html, width, AnsiColor, Color, Effect, Hyperlink, Palette, Style, StyledStr};
+
+/// Options for the SVG renderer.
+///
+/// See the [module documentation](index.html) for more information.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Options {
+    /// The palette that is used to convert ANSI colors to RGB values.
+    pub palette: Palette,
+    /// The default foreground color.
+    pub fg: Color,
+    /// The background color of the image.
+    pub bg: Color,
+    /// The font family.
+    pub font_family: String,
+    /// The font size in pixels.
+    pub font_size: f32,
+    /// The width of a character cell in pixels.
+    pub cell_width: f32,
+    /// The height of a character cell in pixels.
+    pub cell_height: f32,
+    /// The padding around the text in pixels.
+    pub padding: f32,
+}
+
+impl Options {
+    /// Creates new options with the default values:  the VGA palette, white text on a black
+    /// background, a 14 pixel monospace font and a padding of 10 pixels.
+    pub fn new() -> Options {
+        Default::default()
+    }
+
+    /// Sets the palette that is used to convert ANSI colors to RGB values.
+    pub fn palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    /// Sets the default foreground and background colors.
+    pub fn colors(mut self, fg: Color, bg: Color) -> Self {
+        self.fg = fg;
+        self.bg = bg;
+        self
+    }
+
+    /// Sets the font family and the font size and adjusts the cell size to the font size.
+    pub fn font(mut self, family: impl Into<String>, size: f32) -> Self {
+        self.font_family = family.into();
+        self.font_size = size;
+        self.cell_width = size * 0.6;
+        self.cell_height = size * 1.2;
+        self
+    }
+
+    /// Sets the padding around the text.
+    pub fn padding(mut self, padding: f32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    fn render(&self, w: &mut impl io::Write, rows: &[Vec<Segment>]) -> io::Result<()> {
+        let columns = rows
+            .iter()
+            .filter_map(|row| row.last())
+            .map(|segment| segment.column + segment.width)
+            .max()
+            .unwrap_or(0);
+        let width = 2.0 * self.padding + columns as f32 * self.cell_width;
+        let height = 2.0 * self.padding + rows.len() as f32 * self.cell_height;
+
+        writeln!(
+            w,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" \
+             viewBox=\"0 0 {0} {1}\" font-family=\"{2}\" font-size=\"{3}\">",
+            round(width),
+            round(height),
+            html::escape(&self.font_family),
+            round(self.font_size)
+        )?;
+        writeln!(
+            w,
+            "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>",
+            self.palette.hex(self.bg)
+        )?;
+
+        for (row, segments) in rows.iter().enumerate() {
+            for segment in segments {
+                let (_, bg) = self.get_colors(&segment.style);
+                if let Some(bg) = bg {
+                    writeln!(
+                        w,
+                        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                        self.get_x(segment.column),
+                        round(self.padding + row as f32 * self.cell_height),
+                        round(segment.width as f32 * self.cell_width),
+                        round(self.cell_height),
+                        self.palette.hex(bg)
+                    )?;
+                }
+            }
+        }
+
+        for (row, segments) in rows.iter().enumerate() {
+            if segments.is_empty() {
+                continue;
+            }
+            // place the baseline so that the font is vertically centered in the cell
+            let baseline = self.padding
+                + row as f32 * self.cell_height
+                + (self.cell_height + self.font_size) / 2.0
+                - self.font_size * 0.2;
+            write!(
+                w,
+                "<text y=\"{}\" fill=\"{}\" xml:space=\"preserve\">",
+                round(baseline),
+                self.palette.hex(self.fg)
+            )?;
+            for segment in segments {
+                self.render_segment(w, segment)?;
+            }
+            writeln!(w, "</text>")?;
+        }
+
+        writeln!(w, "</svg>")
+    }
+
+    fn render_segment(&self, w: &mut impl io::Write, segment: &Segment) -> io::Result<()> {
+        let link = segment
+            .link
+            .as_ref()
+            .filter(|link| html::is_safe_url(&link.url));
+        if let Some(link) = link {
+            write!(w, "<a href=\"{}\">", html::escape(&link.url))?;
+        }
+        write!(w, "<tspan x=\"{}\"", self.get_x(segment.column))?;
+        let (fg, _) = self.get_colors(&segment.style);
+        if let Some(fg) = fg {
+            write!(w, " fill=\"{}\"", self.palette.hex(fg))?;
+        }
+        let effects = segment.style.effects;
+        if effects.is_set(Effect::Bold) {
+            write!(w, " font-weight=\"bold\"")?;
+        }
+        if effects.is_set(Effect::Italic) {
+            write!(w, " font-style=\"italic\"")?;
+        }
+        let decorations: Vec<_> = [
+            (Effect::Underline, "underline"),
+            (Effect::Strikethrough, "line-through"),
+            (Effect::Overline, "overline"),
+        ]
+        .iter()
+        .filter(|(effect, _)| effects.is_set(*effect))
+        .map(|(_, value)| *value)
+        .collect();
+        if !decorations.is_empty() {
+            write!(w, " text-decoration=\"{}\"", decorations.join(" "))?;
+        }
+        if effects.is_set(Effect::Dim) {
+            write!(w, " fill-opacity=\"0.5\"")?;
+        }
+        if effects.is_set(Effect::Hidden) {
+            write!(w, " visibility=\"hidden\"")?;
+        }
+        write!(w, ">{}</tspan>", html::escape(&segment.s))?;
+        if link.is_some() {
+            write!(w, "</a>")?;
+        }
+        Ok(())
+    }
+
+    /// Returns the foreground and background color for the given style if they differ from the
+    /// default colors.
+    fn get_colors(&self, style: &Style) -> (Option<Color>, Option<Color>) {
+        if style.effects.is_set(Effect::Reverse) {
+            (
+                Some(style.bg.unwrap_or(self.bg)),
+                Some(style.fg.unwrap_or(self.fg)),
+            )
+        } else {
+            (style.fg, style.bg)
+        }
+    }
+
+    fn get_x(&self, column: usize) -> f32 {
+        round(self.padding + column as f32 * self.cell_width)
+    }
+}
+
+/// Rounds the given coordinate to two decimal places to avoid noise in the output.
+fn round(x: f32) -> f32 {
+    (x * 100.0).round() / 100.0
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            palette: Palette::default(),
+            fg: AnsiColor::White.dark(),
+            bg: AnsiColor::Black.dark(),
+            font_family: "monospace".to_owned(),
+            font_size: 14.0,
+            cell_width: 8.4,
+            cell_height: 16.8,
+            padding: 10.0,
+        }
+    }
+}
+
+/// A part of a styled string that is placed in a single row.
+#[derive(Clone, Debug)]
+struct Segment {
+    column: usize,
+    width: usize,
+    s: String,
+    style: Style,
+    link: Option<Hyperlink<'static>>,
+}
+
+/// Splits the given strings into rows of segments.
+fn layout<'a>(iter: impl Iterator<Item = StyledStr<'a>>) -> Vec<Vec<Segment>> {
+    let mut rows = vec![Vec::new()];
+    let mut column = 0;
+    for s in iter {
+        for (i, part) in s.s.split('\n').enumerate() {
+            if i > 0 {
+                rows.push(Vec::new());
+                column = 0;
+            }
+            // control characters are not allowed in XML documents and have no width
+            let part: String = width::expand_tabs(part, 8, column)
+                .chars()
+                .filter(|c| !c.is_control())
+                .collect();
+            if !part.is_empty() {
+                let width = width::str_width(&part);
+                if let Some(row) = rows.last_mut() {
+                    row.push(Segment {
+                        column,
+                        width,
+                        s: part,
+                        style: s.style.unwrap_or_default(),
+                        link: s.link.clone().map(Hyperlink::into_owned),
+                    });
+                }
+                column += width;
+            }
+        }
+    }
+    if rows.len() > 1 && rows.last().map(Vec::is_empty).unwrap_or_default() {
+        rows.pop();
+    }
+    rows
+}
+
+/// Renders a styled string to the given output as an SVG image using the default options.
+///
+/// # Example
+///
+/// ```
+/// let s = text_style::StyledStr::plain("test").bold();
+/// text_style::svg::render(std::io::stdout(), s)
+///     .expect("Failed to render image");
+/// ```
+pub fn render<'a>(w: impl io::Write, s: impl Into<StyledStr<'a>>) -> io::Result<()> {
+    render_iter(w, iter::once(s))
+}
+
+/// Renders a styled string to the given output as an SVG image using the given options.
+pub fn render_with<'a>(
+    w: impl io::Write,
+    s: impl Into<StyledStr<'a>>,
+    options: &Options,
+) -> io::Result<()> {
+    render_iter_with(w, iter::once(s), options)
+}
+
+/// Renders multiple styled string to the given output as an SVG image using the default options.
+///
+/// # Example
+///
+/// ```
+/// let v = vec![
+///     text_style::StyledStr::plain("test").bold(),
+///     text_style::StyledStr::plain("\n"),
+///     text_style::StyledStr::plain("test2").italic(),
+/// ];
+/// text_style::svg::render_iter(std::io::stdout(), v.iter())
+///     .expect("Failed to render image");
+/// ```
+pub fn render_iter<'a, I, Iter, S, W>(w: W, iter: I) -> io::Result<()>
+where
+    I: IntoIterator<Item = S, IntoIter = Iter>,
+    Iter: Iterator<Item = S>,
+    S: Into<StyledStr<'a>>,
+    W: io::Write,
+{
+    render_iter_with(w, iter, &Options::new())
+}
+
+/// Renders multiple styled string to the given output as an SVG image using the given options.
+pub fn render_iter_with<'a, I, Iter, S, W>(mut w: W, iter: I, options: &Options) -> io::Result<()>
+where
+    I: IntoIterator<Item = S, IntoIter = Iter>,
+    Iter: Iterator<Item = S>,
+    S: Into<StyledStr<'a>>,
+    W: io::Write,
+{
+    let rows = layout(iter.into_iter().map(Into::into));
+    options.render(&mut w, &rows)
+}
@@ -11,6 +11,24 @@
 //! - [`syntect::highlighting::Style`][] to [`Style`][]
 //! - `(&str, syntect::highlighting::Style)` to [`StyledStr`][]
 //!
+//! These conversions always set the background color and ignore the alpha channel of the colors.
+//! If you want to omit the default background of the theme so that the background of the
+//! terminal is visible, or if you want to blend translucent colors, use a [`Converter`][]
+//! instead:
+//!
+//! ```
+//! use syntect::highlighting;
+//! use text_style::{syntect::Converter, AnsiColor};
+//!
+//! let ts = highlighting::ThemeSet::load_defaults();
+//! let converter = Converter::new()
+//!     .omit_theme_background(&ts.themes["base16-ocean.dark"])
+//!     .blend(AnsiColor::Black.dark());
+//! ```
+//!
+//! If the `syntect-parsing` feature is enabled, the [`HighlightedLines`][] iterator can be used to
+//! highlight a text and to convert the highlighted lines to owned styled strings.
+//!
 //! # Example
 //!
 //! Converting highlighted ranges to styled strings and rendering them:
@@ -35,14 +53,251 @@
 //! [`syntect::highlighting::Color`]: https://docs.rs/syntect/latest/syntect/highlighting/struct.Color.html
 //! [`syntect::highlighting::FontStyle`]: https://docs.rs/syntect/latest/syntect/highlighting/struct.Style.html
 //! [`syntect::highlighting::Style`]: https://docs.rs/syntect/latest/syntect/highlighting/struct.Style.html
+//! [`Converter`]: struct.Converter.html
+//! [`HighlightedLines`]: struct.HighlightedLines.html
 //! [`Color`]: ../enum.Color.html
 //! [`Effects`]: ../struct.Effects.html
 //! [`Style`]: ../struct.Style.html
 //! [`StyledStr`]: ../struct.StyledStr.html
 
 use syntect::highlighting;
+#[cfg(feature = "syntect-parsing")]
+use syntect::{easy, parsing, util};
+
+use crate::{palette, Color, Effects, Style, StyledStr};
+#[cfg(feature = "syntect-parsing")]
+use crate::{width, StyledString, StyledText};
+
+/// A configurable converter for [`syntect`][]’s text style types.
+///
+/// Per default, the converter behaves like the `From` implementations in this module:  It always
+/// sets the background color and ignores the alpha channel of the colors.  Use
+/// [`omit_background`][] or [`omit_theme_background`][] to drop a background color, and
+/// [`blend`][] to blend translucent colors with a base color.
+///
+/// # Example
+///
+/// ```
+/// use syntect::highlighting::{Color, FontStyle, Style};
+/// use text_style::{syntect::Converter, Effect};
+///
+/// let white = Color { r: 0xff, g: 0xff, b: 0xff, a: 0xff };
+/// let black = Color { r: 0x00, g: 0x00, b: 0x00, a: 0xff };
+/// let style = Style {
+///     foreground: Color { a: 0x80, ..white },
+///     background: black,
+///     font_style: FontStyle::BOLD,
+/// };
+///
+/// let converter = Converter::new().omit_background(black).blend(text_style::Color::Rgb {
+///     r: 0x00,
+///     g: 0x00,
+///     b: 0x00,
+/// });
+/// assert_eq!(
+///     converter.style(style),
+///     text_style::Style::fg(text_style::Color::Rgb { r: 0x80, g: 0x80, b: 0x80 })
+///         .and(text_style::Style::effect(Effect::Bold)),
+/// );
+/// ```
+///
+/// [`syntect`]: https://docs.rs/syntect
+/// [`omit_background`]: #method.omit_background
+/// [`omit_theme_background`]: #method.omit_theme_background
+/// [`blend`]: #method.blend
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Converter {
+    /// The background color that is omitted in the converted styles.
+    pub omit_background: Option<highlighting::Color>,
+    /// The base color for blending the alpha channel of the colors, or `None` if the alpha channel
+    /// should be ignored.
+    pub blend: Option<Color>,
+}
+
+impl Converter {
+    /// Creates a new converter that keeps all background colors and ignores the alpha channel.
+    pub fn new() -> Converter {
+        Default::default()
+    }
+
+    /// Omits background colors that are equal to the given color.
+    ///
+    /// The colors are compared before blending, including the alpha channel.
+    pub fn omit_background(mut self, color: highlighting::Color) -> Self {
+        self.omit_background = Some(color);
+        self
+    }
+
+    /// Omits background colors that are equal to the default background color of the given theme.
+    ///
+    /// If the theme does not define a background color, this method does not change the
+    /// converter.
+    pub fn omit_theme_background(mut self, theme: &highlighting::Theme) -> Self {
+        if let Some(color) = theme.settings.background {
+            self.omit_background = Some(color);
+        }
+        self
+    }
+
+    /// Blends translucent colors with the given base color.
+    ///
+    /// The background color is blended with the base color, and the foreground color is blended
+    /// with the resulting background color, even if it is omitted.  Non-RGB base colors are
+    /// converted using the VGA palette, see [`palette::rgb`][].
+    ///
+    /// [`palette::rgb`]: ../palette/fn.rgb.html
+    pub fn blend(mut self, base: impl Into<Color>) -> Self {
+        self.blend = Some(base.into());
+        self
+    }
+
+    /// Converts the given `syntect` style.
+    pub fn style(&self, style: highlighting::Style) -> Style {
+        let (fg, bg) = if let Some(base) = self.blend {
+            let bg = blend(style.background, palette::rgb(base));
+            let fg = blend(style.foreground, bg);
+            (rgb_color(fg), rgb_color(bg))
+        } else {
+            (style.foreground.into(), style.background.into())
+        };
+        let bg = if self.omit_background == Some(style.background) {
+            None
+        } else {
+            Some(bg)
+        };
+        Style {
+            fg: Some(fg),
+            bg,
+            effects: style.font_style.into(),
+            ..Default::default()
+        }
+    }
 
-use crate::{Color, Effects, Style, StyledStr};
+    /// Converts the given string with a `syntect` style, as returned by the
+    /// [`syntect::easy::HighlightLines::highlight`][] method, to a styled string.
+    ///
+    /// [`syntect::easy::HighlightLines::highlight`]: https://docs.rs/syntect/latest/syntect/easy/struct.HighlightLines.html#method.highlight
+    pub fn styled_str<'a>(&self, (style, s): (highlighting::Style, &'a str)) -> StyledStr<'a> {
+        StyledStr::styled(s, self.style(style))
+    }
+}
+
+/// An iterator over the highlighted lines of a text.
+///
+/// *Requires the `syntect-parsing` feature.*
+///
+/// This iterator highlights the lines of a text using [`syntect::easy::HighlightLines`][] and
+/// yields the highlighted lines as owned styled strings.  The lines include the trailing newline,
+/// if any.  The styles are converted using a [`Converter`][], see [`converter`][].  If
+/// [`merge`][] is enabled, adjacent strings with the same style are merged.
+///
+/// # Example
+///
+/// ```
+/// use syntect::{highlighting, parsing};
+/// use text_style::syntect::{Converter, HighlightedLines};
+///
+/// let ps = parsing::SyntaxSet::load_defaults_newlines();
+/// let ts = highlighting::ThemeSet::load_defaults();
+///
+/// let syntax = ps.find_syntax_by_extension("rs").unwrap();
+/// let theme = &ts.themes["base16-ocean.dark"];
+/// let s = "pub struct Wow { hi: u64 }\nfn blah() -> u64 {}";
+/// let lines: Vec<_> = HighlightedLines::new(&ps, syntax, theme, s)
+///     .converter(Converter::new().omit_theme_background(theme))
+///     .merge(true)
+///     .collect();
+/// assert_eq!(lines.len(), 2);
+/// for line in &lines {
+///     text_style::ansi_term::render_iter(std::io::stdout(), line)
+///         .expect("Could not render line");
+/// }
+/// ```
+///
+/// [`syntect::easy::HighlightLines`]: https://docs.rs/syntect/latest/syntect/easy/struct.HighlightLines.html
+/// [`Converter`]: struct.Converter.html
+/// [`converter`]: #method.converter
+/// [`merge`]: #method.merge
+#[cfg(feature = "syntect-parsing")]
+pub struct HighlightedLines<'a> {
+    highlighter: easy::HighlightLines<'a>,
+    syntax_set: &'a parsing::SyntaxSet,
+    lines: util::LinesWithEndings<'a>,
+    converter: Converter,
+    merge: bool,
+}
+
+#[cfg(feature = "syntect-parsing")]
+impl<'a> HighlightedLines<'a> {
+    /// Creates a new iterator over the lines of the given text, highlighted with the given syntax
+    /// and theme.
+    pub fn new(
+        syntax_set: &'a parsing::SyntaxSet,
+        syntax: &parsing::SyntaxReference,
+        theme: &'a highlighting::Theme,
+        s: &'a str,
+    ) -> HighlightedLines<'a> {
+        HighlightedLines {
+            highlighter: easy::HighlightLines::new(syntax, theme),
+            syntax_set,
+            lines: util::LinesWithEndings::from(s),
+            converter: Converter::new(),
+            merge: false,
+        }
+    }
+
+    /// Sets the converter for the `syntect` styles.
+    pub fn converter(mut self, converter: Converter) -> Self {
+        self.converter = converter;
+        self
+    }
+
+    /// Sets whether adjacent strings with the same style should be merged.
+    ///
+    /// If this is enabled, empty strings are removed too.
+    pub fn merge(mut self, merge: bool) -> Self {
+        self.merge = merge;
+        self
+    }
+}
+
+#[cfg(feature = "syntect-parsing")]
+impl<'a> Iterator for HighlightedLines<'a> {
+    type Item = Vec<StyledString>;
+
+    fn next(&mut self) -> Option<Vec<StyledString>> {
+        let line = self.lines.next()?;
+        let ranges = self.highlighter.highlight(line, self.syntax_set);
+        let strings = ranges
+            .into_iter()
+            .map(|range| StyledString::from(self.converter.styled_str(range)));
+        if self.merge {
+            let mut text = StyledText::new();
+            for s in strings {
+                width::push_merged(&mut text, s);
+            }
+            Some(text.into_spans())
+        } else {
+            Some(strings.collect())
+        }
+    }
+}
+
+fn blend(color: highlighting::Color, (r, g, b): (u8, u8, u8)) -> (u8, u8, u8) {
+    let blend_channel = |fg: u8, bg: u8| {
+        let a = u16::from(color.a);
+        ((a * u16::from(fg) + (255 - a) * u16::from(bg)) / 255) as u8
+    };
+    (
+        blend_channel(color.r, r),
+        blend_channel(color.g, g),
+        blend_channel(color.b, b),
+    )
+}
+
+fn rgb_color((r, g, b): (u8, u8, u8)) -> Color {
+    Color::Rgb { r, g, b }
+}
 
 impl From<highlighting::Color> for Color {
     fn from(color: highlighting::Color) -> Color {
@@ -60,18 +315,14 @@ impl From<highlighting::FontStyle> for Effects {
             is_bold: font_style.contains(highlighting::FontStyle::BOLD),
             is_italic: font_style.contains(highlighting::FontStyle::ITALIC),
             is_underline: font_style.contains(highlighting::FontStyle::UNDERLINE),
-            is_strikethrough: false,
+            ..Default::default()
         }
     }
 }
 
 impl From<highlighting::Style> for Style {
     fn from(style: highlighting::Style) -> Style {
-        Style {
-            fg: Some(style.foreground.into()),
-            bg: Some(style.background.into()),
-            effects: style.font_style.into(),
-        }
+        Converter::new().style(style)
     }
 }
 
@@ -80,6 +331,8 @@ impl<'a, 'b> From<&'b (highlighting::Style, &'a str)> for StyledStr<'a> {
         StyledStr {
             s,
             style: Some(Style::from(*style)),
+            link: None,
+            style_name: None,
         }
     }
 }
@@ -89,6 +342,8 @@ impl<'a> From<(highlighting::Style, &'a str)> for StyledStr<'a> {
         StyledStr {
             s,
             style: Some(style.into()),
+            link: None,
+            style_name: None,
         }
     }
 }
@@ -0,0 +1,517 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+//! Tables of styled text.
+//!
+//! This module provides the [`Table`][] struct that lays out cells of styled text in columns.  The
+//! result is a list of lines of styled strings that can be rendered with any backend.  The widths
+//! of the columns are determined by the display width of the cells, see the [`width`][] module,
+//! and can be limited for each column using a [`Column`][].  Cells that are wider than their
+//! column are either wrapped, see the [`wrap`][] module, or truncated.
+//!
+//! Optionally, a table can have a header row with a separate style, a [`Border`][] drawn with
+//! ASCII or box-drawing characters and a style for every second row (zebra striping).  The header
+//! style and the stripe style are combined with the styles of the cells using [`Style::and`][].
+//!
+//! # Example
+//!
+//! ```
+//! use text_style::{table, AnsiColor, Effect, Style, StyledStr};
+//!
+//! let table = table::Table::new()
+//!     .column(table::Column::new())
+//!     .column(table::Column::new().align(table::Alignment::Right))
+//!     .header(vec![StyledStr::plain("Name"), StyledStr::plain("Size")])
+//!     .header_style(Style::effect(Effect::Bold))
+//!     .row(vec![StyledStr::plain("Cargo.toml"), StyledStr::plain("1.2 kB")])
+//!     .row(vec![StyledStr::plain("src").with(AnsiColor::Blue.light()), "4 kB".into()])
+//!     .border(table::Border::single());
+//! for line in table.lines() {
+//!     text_style::ansi::render_iter(std::io::stdout(), line.iter())
+//!         .expect("Failed to render line");
+//!     println!();
+//! }
+//!
+//! let lines: Vec<_> = table.lines().iter().map(|line| line.plain_text()).collect();
+//! assert_eq!(lines, vec![
+//!     "┌────────────┬────────┐",
+//!     "│ Name       │   Size │",
+//!     "├────────────┼────────┤",
+//!     "│ Cargo.toml │ 1.2 kB │",
+//!     "│ src        │   4 kB │",
+//!     "└────────────┴────────┘",
+//! ]);
+//! ```
+//!
+//! [`Border`]: struct.Border.html
+//! [`Column`]: struct.Column.html
+//! [`Style::and`]: ../struct.Style.html#method.and
+//! [`Table`]: struct.Table.html
+//! [`width`]: ../width/index.html
+//! [`wrap`]: ../wrap/index.html
+
+use std::cmp;
+
+use crate::width::{self, push_merged};
+use crate::{wrap, Style, StyledString, StyledText};
+
+/// The horizontal alignment of the cells of a column.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Alignment {
+    /// Align the cells at the left border of the column.
+    Left,
+    /// Center the cells in the column.
+    Center,
+    /// Align the cells at the right border of the column.
+    Right,
+}
+
+impl Default for Alignment {
+    fn default() -> Alignment {
+        Alignment::Left
+    }
+}
+
+/// The handling of cells that are wider than their column.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Overflow {
+    /// Wrap the cells into multiple lines, see the [`wrap`](../wrap/index.html) module.
+    Wrap,
+    /// Truncate the cells and append an ellipsis, see
+    /// [`width::truncate`](../width/fn.truncate.html).
+    Truncate,
+}
+
+impl Default for Overflow {
+    fn default() -> Overflow {
+        Overflow::Wrap
+    }
+}
+
+/// The layout settings of a table column.
+///
+/// The width of a column is the display width of its widest cell, limited by `min_width` and
+/// `max_width`.  If `max_width` is smaller than `min_width`, `min_width` is used.  Grapheme
+/// clusters that are wider than the column cannot be wrapped or truncated, so lines containing
+/// them overflow the column.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Column {
+    /// The alignment of the cells.
+    pub alignment: Alignment,
+    /// The minimum width of the column.
+    pub min_width: usize,
+    /// The maximum width of the column, or `None` if the width is not limited.
+    pub max_width: Option<usize>,
+    /// The handling of cells that are wider than `max_width`.
+    pub overflow: Overflow,
+}
+
+impl Column {
+    /// Creates a new left-aligned column without width limits that wraps its cells.
+    pub fn new() -> Column {
+        Default::default()
+    }
+
+    /// Sets the alignment of the cells.
+    pub fn align(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Sets the minimum width of the column.
+    pub fn min_width(mut self, min_width: usize) -> Self {
+        self.min_width = min_width;
+        self
+    }
+
+    /// Sets the maximum width of the column.
+    pub fn max_width(mut self, max_width: usize) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    /// Sets the handling of cells that are wider than the maximum width.
+    pub fn overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    fn get_width(&self, content_width: usize) -> usize {
+        let width = match self.max_width {
+            Some(max_width) => cmp::min(content_width, max_width),
+            None => content_width,
+        };
+        cmp::max(width, self.min_width)
+    }
+}
+
+/// The characters that are used to draw the border of a table.
+///
+/// The border is drawn around the table, between the columns and between the header and the
+/// first row.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Border {
+    /// The horizontal line.
+    pub horizontal: char,
+    /// The vertical line.
+    pub vertical: char,
+    /// The top left corner.
+    pub top_left: char,
+    /// The junction of the top line and a vertical line.
+    pub top: char,
+    /// The top right corner.
+    pub top_right: char,
+    /// The junction of the left line and the header separator.
+    pub left: char,
+    /// The junction of the header separator and a vertical line.
+    pub cross: char,
+    /// The junction of the right line and the header separator.
+    pub right: char,
+    /// The bottom left corner.
+    pub bottom_left: char,
+    /// The junction of the bottom line and a vertical line.
+    pub bottom: char,
+    /// The bottom right corner.
+    pub bottom_right: char,
+}
+
+impl Border {
+    /// Returns a border drawn with the ASCII characters `-`, `|` and `+`.
+    pub fn ascii() -> Border {
+        Border::from_chars('-', '|', ['+'; 9])
+    }
+
+    /// Returns a border drawn with single box-drawing lines.
+    pub fn single() -> Border {
+        Border::from_chars('─', '│', ['┌', '┬', '┐', '├', '┼', '┤', '└', '┴', '┘'])
+    }
+
+    /// Returns a border drawn with single box-drawing lines and rounded corners.
+    pub fn rounded() -> Border {
+        Border::from_chars('─', '│', ['╭', '┬', '╮', '├', '┼', '┤', '╰', '┴', '╯'])
+    }
+
+    /// Returns a border drawn with double box-drawing lines.
+    pub fn double() -> Border {
+        Border::from_chars('═', '║', ['╔', '╦', '╗', '╠', '╬', '╣', '╚', '╩', '╝'])
+    }
+
+    fn from_chars(horizontal: char, vertical: char, junctions: [char; 9]) -> Border {
+        Border {
+            horizontal,
+            vertical,
+            top_left: junctions[0],
+            top: junctions[1],
+            top_right: junctions[2],
+            left: junctions[3],
+            cross: junctions[4],
+            right: junctions[5],
+            bottom_left: junctions[6],
+            bottom: junctions[7],
+            bottom_right: junctions[8],
+        }
+    }
+}
+
+/// A table of styled text.
+///
+/// Rows that have fewer cells than the table has columns are filled with empty cells.  Columns
+/// without settings use the default [`Column`][] settings.  See the [module
+/// documentation](index.html) for more information.
+///
+/// [`Column`]: struct.Column.html
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Table {
+    /// The layout settings of the columns.
+    pub columns: Vec<Column>,
+    /// The cells of the header row, if any.
+    pub header: Option<Vec<StyledText>>,
+    /// The cells of the rows of the table.
+    pub rows: Vec<Vec<StyledText>>,
+    /// The style of the header row.
+    pub header_style: Option<Style>,
+    /// The style of every second row, starting with the second row.
+    pub stripe_style: Option<Style>,
+    /// The border of the table, if any.
+    pub border: Option<Border>,
+    /// The style of the border.
+    pub border_style: Option<Style>,
+    /// The number of spaces on each side of a cell.
+    ///
+    /// Without a border, the padding is only added between the columns.
+    pub padding: usize,
+}
+
+impl Table {
+    /// Creates a new empty table without a border and with a padding of one space.
+    pub fn new() -> Table {
+        Table {
+            padding: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Adds a column with the given settings.
+    pub fn column(mut self, column: Column) -> Self {
+        self.columns.push(column);
+        self
+    }
+
+    /// Sets the cells of the header row.
+    pub fn header<I, S>(mut self, cells: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<StyledText>,
+    {
+        self.header = Some(cells.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Adds a row with the given cells.
+    pub fn row<I, S>(mut self, cells: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<StyledText>,
+    {
+        self.push_row(cells);
+        self
+    }
+
+    /// Adds a row with the given cells.
+    pub fn push_row<I, S>(&mut self, cells: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<StyledText>,
+    {
+        self.rows.push(cells.into_iter().map(Into::into).collect());
+    }
+
+    /// Sets the style of the header row.
+    pub fn header_style(mut self, style: Style) -> Self {
+        self.header_style = Some(style);
+        self
+    }
+
+    /// Sets the style of every second row.
+    pub fn stripes(mut self, style: Style) -> Self {
+        self.stripe_style = Some(style);
+        self
+    }
+
+    /// Sets the border of the table.
+    pub fn border(mut self, border: Border) -> Self {
+        self.border = Some(border);
+        self
+    }
+
+    /// Sets the style of the border.
+    pub fn border_style(mut self, style: Style) -> Self {
+        self.border_style = Some(style);
+        self
+    }
+
+    /// Sets the number of spaces on each side of a cell.
+    pub fn padding(mut self, padding: usize) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Lays out the table and returns its lines.
+    ///
+    /// The lines do not contain a trailing newline.
+    pub fn lines(&self) -> Vec<StyledText> {
+        let widths = self.get_widths();
+        let mut lines = Vec::new();
+
+        if let Some(border) = &self.border {
+            lines.push(self.rule(
+                &widths,
+                border.top_left,
+                border.top,
+                border.top_right,
+                border.horizontal,
+            ));
+        }
+        if let Some(header) = &self.header {
+            self.push_row_lines(&mut lines, &widths, header, self.header_style);
+            if let Some(border) = &self.border {
+                lines.push(self.rule(
+                    &widths,
+                    border.left,
+                    border.cross,
+                    border.right,
+                    border.horizontal,
+                ));
+            }
+        }
+        for (i, row) in self.rows.iter().enumerate() {
+            let style = if i % 2 == 1 { self.stripe_style } else { None };
+            self.push_row_lines(&mut lines, &widths, row, style);
+        }
+        if let Some(border) = &self.border {
+            lines.push(self.rule(
+                &widths,
+                border.bottom_left,
+                border.bottom,
+                border.bottom_right,
+                border.horizontal,
+            ));
+        }
+
+        lines
+    }
+
+    fn get_column(&self, idx: usize) -> Column {
+        self.columns.get(idx).copied().unwrap_or_default()
+    }
+
+    fn get_widths(&self) -> Vec<usize> {
+        let n = self
+            .header
+            .iter()
+            .chain(self.rows.iter())
+            .map(Vec::len)
+            .max()
+            .unwrap_or_default();
+        let n = cmp::max(n, self.columns.len());
+        let mut widths = vec![0; n];
+        for row in self.header.iter().chain(self.rows.iter()) {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                let cell_width = split_lines(cell)
+                    .iter()
+                    .map(StyledText::width)
+                    .max()
+                    .unwrap_or_default();
+                *width = cmp::max(*width, cell_width);
+            }
+        }
+        for (i, width) in widths.iter_mut().enumerate() {
+            *width = self.get_column(i).get_width(*width);
+        }
+        widths
+    }
+
+    /// Returns the lines of the given cell that are not wider than the given width.
+    fn get_cell_lines(&self, cell: &StyledText, column: &Column, width: usize) -> Vec<StyledText> {
+        let mut lines = Vec::new();
+        for line in split_lines(cell) {
+            if line.width() <= width {
+                lines.push(line);
+            } else if column.overflow == Overflow::Truncate {
+                lines.push(width::truncate(line.iter(), width, "…"));
+            } else {
+                lines.extend(wrap::wrap(line.iter(), &wrap::Options::new(width)));
+            }
+        }
+        lines
+    }
+
+    fn push_row_lines(
+        &self,
+        lines: &mut Vec<StyledText>,
+        widths: &[usize],
+        row: &[StyledText],
+        style: Option<Style>,
+    ) {
+        let empty = StyledText::new();
+        let cells: Vec<_> = widths
+            .iter()
+            .enumerate()
+            .map(|(i, width)| {
+                let cell = row.get(i).unwrap_or(&empty);
+                self.get_cell_lines(cell, &self.get_column(i), *width)
+            })
+            .collect();
+        let height = cells.iter().map(Vec::len).max().unwrap_or_default();
+        let height = cmp::max(height, 1);
+
+        for row_idx in 0..height {
+            let mut line = StyledText::new();
+            if let Some(border) = &self.border {
+                self.push_border(&mut line, border.vertical);
+                push_spaces(&mut line, self.padding, style);
+            }
+            for (i, width) in widths.iter().enumerate() {
+                if i > 0 {
+                    push_spaces(&mut line, self.padding, style);
+                    if let Some(border) = &self.border {
+                        self.push_border(&mut line, border.vertical);
+                    }
+                    push_spaces(&mut line, self.padding, style);
+                }
+                let cell_line = cells[i].get(row_idx).unwrap_or(&empty);
+                // the line can be wider than the column if it contains a grapheme cluster that
+                // is wider than the column
+                let space = width.saturating_sub(cell_line.width());
+                let (left, right) = match self.get_column(i).alignment {
+                    Alignment::Left => (0, space),
+                    Alignment::Center => (space / 2, space - space / 2),
+                    Alignment::Right => (space, 0),
+                };
+                push_spaces(&mut line, left, style);
+                for s in cell_line {
+                    let mut s = s.clone();
+                    if let Some(style) = style {
+                        s.style = Some(style.and(s.style.unwrap_or_default()));
+                    }
+                    push_merged(&mut line, s);
+                }
+                push_spaces(&mut line, right, style);
+            }
+            if let Some(border) = &self.border {
+                push_spaces(&mut line, self.padding, style);
+                self.push_border(&mut line, border.vertical);
+            }
+            lines.push(line);
+        }
+    }
+
+    fn rule(
+        &self,
+        widths: &[usize],
+        left: char,
+        junction: char,
+        right: char,
+        horizontal: char,
+    ) -> StyledText {
+        let mut s = String::new();
+        s.push(left);
+        for (i, width) in widths.iter().enumerate() {
+            if i > 0 {
+                s.push(junction);
+            }
+            s.push_str(&horizontal.to_string().repeat(width + 2 * self.padding));
+        }
+        s.push(right);
+        StyledString::new(s, self.border_style).into()
+    }
+
+    fn push_border(&self, line: &mut StyledText, c: char) {
+        push_merged(line, StyledString::new(c.to_string(), self.border_style));
+    }
+}
+
+fn push_spaces(line: &mut StyledText, n: usize, style: Option<Style>) {
+    push_merged(line, StyledString::new(" ".repeat(n), style));
+}
+
+/// Splits the given text at newlines.  A trailing newline does not add an empty line.
+fn split_lines(text: &StyledText) -> Vec<StyledText> {
+    let mut lines = vec![StyledText::new()];
+    for s in text {
+        for (i, part) in s.s.split('\n').enumerate() {
+            if i > 0 {
+                lines.push(StyledText::new());
+            }
+            let mut part_s = s.clone();
+            part_s.s = part.to_owned();
+            if let Some(line) = lines.last_mut() {
+                push_merged(line, part_s);
+            }
+        }
+    }
+    if lines.len() > 1 && lines.last().map(StyledText::is_empty).unwrap_or_default() {
+        lines.pop();
+    }
+    lines
+}
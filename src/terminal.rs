@@ -0,0 +1,224 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+//! Detection of the terminal capabilities.
+//!
+//! This module provides the [`Capabilities`][] struct that stores the color depth, the text
+//! effects and the hyperlink support of a terminal.  The capabilities can be detected from the
+//! environment using [`Capabilities::from_env`][].  They can be applied to styled strings so that
+//! they only use the supported colors and effects, either manually using
+//! [`Capabilities::apply`][] or using the `render_with` and `render_iter_with` functions of the
+//! backends.
+//!
+//! # Example
+//!
+//! ```
+//! use text_style::terminal::Capabilities;
+//!
+//! // Use the is_terminal method from std::io::IsTerminal or the atty crate to check whether
+//! // stdout is a terminal.
+//! let is_tty = false;
+//! let capabilities = Capabilities::from_env(is_tty);
+//!
+//! let s = capabilities.apply(text_style::StyledStr::plain("test").bold());
+//! text_style::ansi::render(std::io::stdout(), s)
+//!     .expect("Failed to render string");
+//! ```
+//!
+//! [`Capabilities`]: struct.Capabilities.html
+//! [`Capabilities::apply`]: struct.Capabilities.html#method.apply
+//! [`Capabilities::from_env`]: struct.Capabilities.html#method.from_env
+
+use std::env;
+
+use crate::{ColorDepth, Effect, Effects, Style, StyledStr, StyledString, StyledText, EFFECTS};
+
+/// The capabilities of a terminal: the supported color depth, text effects and hyperlinks.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Capabilities {
+    /// The supported color depth.
+    pub color_depth: ColorDepth,
+    /// The supported text effects.
+    pub effects: Effects,
+    /// Whether hyperlinks are supported.
+    pub hyperlinks: bool,
+}
+
+impl Capabilities {
+    /// Creates a new set of capabilities with the given color depth and text effects and with
+    /// support for hyperlinks.
+    pub fn new(color_depth: ColorDepth, effects: Effects) -> Capabilities {
+        Capabilities {
+            color_depth,
+            effects,
+            hyperlinks: true,
+        }
+    }
+
+    /// Returns the capabilities of a terminal that supports all colors, text effects and
+    /// hyperlinks.
+    pub fn all() -> Capabilities {
+        Capabilities::new(ColorDepth::TrueColor, EFFECTS.iter().copied().collect())
+    }
+
+    /// Returns the capabilities of an output that does not support colors, text effects and
+    /// hyperlinks.
+    pub fn none() -> Capabilities {
+        Capabilities {
+            hyperlinks: false,
+            ..Capabilities::new(ColorDepth::NoColor, Effects::empty())
+        }
+    }
+
+    /// Detects the capabilities of the terminal using the environment variables of the current
+    /// process.
+    ///
+    /// `is_tty` must indicate whether the output is a terminal.  See
+    /// [`from_vars`][`Capabilities::from_vars`] for a description of the detection rules.
+    ///
+    /// [`Capabilities::from_vars`]: #method.from_vars
+    pub fn from_env(is_tty: bool) -> Capabilities {
+        Capabilities::from_vars(is_tty, |name| {
+            env::var_os(name).map(|value| value.to_string_lossy().into_owned())
+        })
+    }
+
+    /// Detects the capabilities of the terminal using the given function to look up environment
+    /// variables.
+    ///
+    /// `is_tty` must indicate whether the output is a terminal.  The capabilities are detected
+    /// using these rules:
+    ///
+    /// - If the output is not a terminal or if `TERM` is set to `dumb`, colors, text effects and
+    ///   hyperlinks are disabled unless `CLICOLOR_FORCE` is set and not `0`.
+    /// - If `CLICOLOR` is set to `0`, colors, text effects and hyperlinks are disabled unless
+    ///   `CLICOLOR_FORCE` is set and not `0`.
+    /// - If `NO_COLOR` is set and not empty, colors are disabled.  Text effects are not affected.
+    /// - If `COLORTERM` is set to `truecolor` or `24bit`, or if `TERM` ends with `-direct`, RGB
+    ///   colors are supported.
+    /// - If `TERM` contains `256color`, the 256-color palette is supported.
+    /// - Otherwise, only the ANSI colors are supported.
+    /// - The Linux console (`TERM=linux`) does not support italic, struckthrough and overlined
+    ///   text and hyperlinks.
+    /// - If `FORCE_HYPERLINK` is set, hyperlinks are enabled unless it is set to `0`.  This
+    ///   overrides all other rules for hyperlinks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use text_style::{terminal::Capabilities, ColorDepth};
+    ///
+    /// let capabilities = Capabilities::from_vars(true, |name| match name {
+    ///     "TERM" => Some("xterm-256color".to_owned()),
+    ///     _ => None,
+    /// });
+    /// assert_eq!(capabilities.color_depth, ColorDepth::Indexed);
+    /// ```
+    pub fn from_vars<F>(is_tty: bool, get_var: F) -> Capabilities
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let term = get_var("TERM").unwrap_or_default();
+        let is_forced = matches!(get_var("CLICOLOR_FORCE"), Some(s) if !s.is_empty() && s != "0");
+        let is_enabled = is_tty && term != "dumb" && get_var("CLICOLOR").as_deref() != Some("0");
+        let force_hyperlink = get_var("FORCE_HYPERLINK").map(|s| s != "0");
+        if !is_enabled && !is_forced {
+            return Capabilities {
+                hyperlinks: force_hyperlink.unwrap_or(false),
+                ..Capabilities::none()
+            };
+        }
+
+        let color_term = get_var("COLORTERM").unwrap_or_default();
+        let color_depth = if matches!(get_var("NO_COLOR"), Some(s) if !s.is_empty()) {
+            ColorDepth::NoColor
+        } else if color_term == "truecolor" || color_term == "24bit" || term.ends_with("-direct") {
+            ColorDepth::TrueColor
+        } else if term.contains("256color") {
+            ColorDepth::Indexed
+        } else {
+            ColorDepth::Ansi
+        };
+
+        let mut capabilities = Capabilities::all();
+        capabilities.color_depth = color_depth;
+        if term == "linux" {
+            capabilities.effects.set(Effect::Italic, false);
+            capabilities.effects.set(Effect::Strikethrough, false);
+            capabilities.effects.set(Effect::Overline, false);
+            capabilities.hyperlinks = false;
+        }
+        if let Some(hyperlinks) = force_hyperlink {
+            capabilities.hyperlinks = hyperlinks;
+        }
+        capabilities
+    }
+
+    /// Checks whether the given text effect is supported.
+    pub fn supports(&self, effect: Effect) -> bool {
+        self.effects.is_set(effect)
+    }
+
+    /// Converts the given style so that it only uses the supported colors and effects.
+    ///
+    /// Colors are converted using [`Style::downgrade`][], unsupported effects are removed.
+    ///
+    /// [`Style::downgrade`]: ../struct.Style.html#method.downgrade
+    pub fn apply_style(&self, style: Style) -> Style {
+        let mut style = style.downgrade(self.color_depth);
+        style.effects = style
+            .effects
+            .into_iter()
+            .filter(|effect| self.supports(*effect))
+            .collect();
+        style
+    }
+
+    /// Converts the given string so that it only uses the supported colors and effects.  If
+    /// hyperlinks are not supported, the hyperlink of the string is removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use text_style::{terminal::Capabilities, AnsiColor, StyledStr};
+    ///
+    /// let s = StyledStr::plain("test").with(AnsiColor::Red.dark()).bold();
+    /// assert_eq!(Capabilities::none().apply(s), StyledStr::plain("test"));
+    /// ```
+    pub fn apply<'a>(&self, s: impl Into<StyledStr<'a>>) -> StyledStr<'a> {
+        let mut s = s.into();
+        s.style = self.apply_optional_style(s.style);
+        if !self.hyperlinks {
+            s.link = None;
+        }
+        s
+    }
+
+    /// Converts the given string so that it only uses the supported colors and effects.  If
+    /// hyperlinks are not supported, the hyperlink of the string is removed.
+    pub fn apply_string(&self, mut s: StyledString) -> StyledString {
+        s.style = self.apply_optional_style(s.style);
+        if !self.hyperlinks {
+            s.link = None;
+        }
+        s
+    }
+
+    /// Converts the given text so that it only uses the supported colors and effects.  If
+    /// hyperlinks are not supported, the hyperlinks of the text are removed.
+    pub fn apply_text(&self, text: StyledText) -> StyledText {
+        text.into_iter().map(|s| self.apply_string(s)).collect()
+    }
+
+    fn apply_optional_style(&self, style: Option<Style>) -> Option<Style> {
+        style
+            .map(|style| self.apply_style(style))
+            .filter(|style| *style != Style::default())
+    }
+}
+
+impl Default for Capabilities {
+    fn default() -> Capabilities {
+        Capabilities::all()
+    }
+}
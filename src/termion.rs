@@ -15,11 +15,24 @@
 //! using its [`Display`][] implementation.
 //!
 //! Alternatively, you can use the [`render`][] function to render a single string and the
-//! [`render_iter`][] function to render an iterator over strings.
+//! [`render_iter`][] function to render an iterator over strings.  The [`render_with`][] and
+//! [`render_iter_with`][] functions convert the strings to the given terminal [`Capabilities`][]
+//! before rendering them.  [`render`][] and [`render_iter`][] write all attributes regardless of
+//! the output, so use [`render_with`][] with [`Capabilities::from_env`][] to respect the terminal
+//! and the `NO_COLOR` and `CLICOLOR` environment variables.
+//! The [`render_themed`][] and [`render_iter_themed`][] functions resolve the semantic styles of
+//! the strings using the given [`Theme`][].
 //!
-//! Note that this implementation always uses [`termion::style::Reset`][] to clear the formatting
-//! instead of [`termion::style::NoBold`][] etc. for compatibility with terminals that don’t
-//! support the *No Bold* style.
+//! termion does not support the [`Hidden`][] and [`Overline`][] effects and underline styles and
+//! colors.  For these settings, the corresponding SGR sequences are written directly.  Hyperlinks
+//! are written as OSC 8 sequences.
+//!
+//! Note that this implementation never uses [`termion::style::NoBold`][] for compatibility with
+//! terminals that don’t support the *No Bold* style.  [`render`][] and [`TermionStr`][] always
+//! use [`termion::style::Reset`][] to clear the formatting.  [`render_iter`][] only writes the
+//! changes between the styles of consecutive strings, using [`termion::style::NoFaint`][] to
+//! disable bold and dim text.  It uses [`termion::style::Reset`][] after the last string and
+//! whenever a reset requires fewer attributes than the changes.
 //!
 //! # Examples
 //!
@@ -43,6 +56,16 @@
 //!     .expect("Failed to render string");
 //! ```
 //!
+//! Rendering a [`StyledText`][]:
+//!
+//! ```
+//! let mut text = text_style::StyledText::new();
+//! text.push(text_style::StyledStr::plain("test").bold());
+//! text.push(text_style::StyledStr::plain(" test2").italic());
+//! text_style::termion::render_iter(std::io::stdout(), &text)
+//!     .expect("Failed to render text");
+//! ```
+//!
 //! Using the [`Termion`][] trait:
 //!
 //! ```
@@ -52,13 +75,24 @@
 //! ```
 //!
 //! [`Display`]: https://doc.rust-lang.org/std/fmt/trait.Display.html
+//! [`Hidden`]: ../enum.Effect.html#variant.Hidden
+//! [`Overline`]: ../enum.Effect.html#variant.Overline
 //! [`termion`]: https://docs.rs/termion
 //! [`termion::style::Reset`]: https://docs.rs/termion/latest/termion/style/struct.Reset.html
 //! [`termion::style::NoBold`]: https://docs.rs/termion/latest/termion/style/struct.NoBold.html
+//! [`termion::style::NoFaint`]: https://docs.rs/termion/latest/termion/style/struct.NoFaint.html
 //! [`StyledStr`]: ../struct.StyledStr.html
 //! [`StyledString`]: ../struct.StyledString.html
+//! [`StyledText`]: ../struct.StyledText.html
 //! [`render`]: fn.render.html
 //! [`render_iter`]: fn.render_iter.html
+//! [`render_with`]: fn.render_with.html
+//! [`render_iter_with`]: fn.render_iter_with.html
+//! [`render_themed`]: fn.render_themed.html
+//! [`render_iter_themed`]: fn.render_iter_themed.html
+//! [`Theme`]: ../theme/struct.Theme.html
+//! [`Capabilities`]: ../terminal/struct.Capabilities.html
+//! [`Capabilities::from_env`]: ../terminal/struct.Capabilities.html#method.from_env
 //! [`Termion`]: trait.Termion.html
 //! [`Termion::termion`]: trait.Termion.html#tymethod.termion
 //! [`TermionStr`]: struct.TermionStr.html
@@ -69,7 +103,12 @@ use std::io;
 
 use termion::{color, style};
 
-use crate::{AnsiColor, AnsiMode, Color, Effect, Style, StyledStr, StyledString};
+use crate::ansi::{self, Change, Transition};
+use crate::terminal::Capabilities;
+use crate::theme::Theme;
+use crate::{
+    AnsiColor, AnsiMode, Color, Effect, Hyperlink, Style, StyledStr, StyledString, UnderlineStyle,
+};
 
 /// A styled string that can be rendered using `termion`.
 ///
@@ -86,10 +125,14 @@ use crate::{AnsiColor, AnsiMode, Color, Effect, Style, StyledStr, StyledString};
 pub struct TermionStr<'a> {
     s: &'a str,
     style: Option<Style>,
+    link: Option<Hyperlink<'a>>,
 }
 
 impl<'a> fmt::Display for TermionStr<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(link) = &self.link {
+            f.write_str(&ansi::hyperlink_start_sequence(link))?;
+        }
         if let Some(style) = &self.style {
             if let Some(fg) = style.fg {
                 f.write_str(get_fg(fg).as_ref())?;
@@ -98,15 +141,30 @@ impl<'a> fmt::Display for TermionStr<'a> {
                 f.write_str(get_bg(bg).as_ref())?;
             }
             for effect in style.effects {
-                f.write_str(get_effect(effect))?;
+                match style.get_underline_style() {
+                    Some(underline_style) if effect == Effect::Underline => {
+                        f.write_str(&ansi::underline_style_sequence(underline_style))?
+                    }
+                    _ => f.write_str(get_effect(effect))?,
+                }
+            }
+            if let Some(color) = style.underline_color {
+                f.write_str(&ansi::underline_color_sequence(color))?;
             }
         }
         f.write_str(self.s)?;
         if let Some(style) = &self.style {
-            if style.fg.is_some() || style.bg.is_some() || !style.effects.is_empty() {
+            if style.fg.is_some()
+                || style.bg.is_some()
+                || style.underline_color.is_some()
+                || !style.effects.is_empty()
+            {
                 f.write_str(style::Reset.as_ref())?;
             }
         }
+        if self.link.is_some() {
+            f.write_str(ansi::HYPERLINK_END_SEQUENCE)?;
+        }
         Ok(())
     }
 }
@@ -140,6 +198,7 @@ impl<'a> Termion for StyledStr<'a> {
         TermionStr {
             s: self.s,
             style: self.style,
+            link: self.link.as_ref().map(Hyperlink::as_borrowed),
         }
     }
 }
@@ -149,6 +208,7 @@ impl Termion for StyledString {
         TermionStr {
             s: &self.s,
             style: self.style,
+            link: self.link.as_ref().map(Hyperlink::as_borrowed),
         }
     }
 }
@@ -156,6 +216,7 @@ impl Termion for StyledString {
 fn get_bg(color: Color) -> borrow::Cow<'static, str> {
     match color {
         Color::Ansi { color, mode } => get_ansi_bg(color, mode).into(),
+        Color::Indexed(index) => color::AnsiValue(index).bg_string().into(),
         Color::Rgb { r, g, b } => color::Rgb(r, g, b).bg_string().into(),
     }
 }
@@ -187,6 +248,7 @@ fn get_ansi_bg(color: AnsiColor, mode: AnsiMode) -> &'static str {
 fn get_fg(color: Color) -> borrow::Cow<'static, str> {
     match color {
         Color::Ansi { color, mode } => get_ansi_fg(color, mode).into(),
+        Color::Indexed(index) => color::AnsiValue(index).fg_string().into(),
         Color::Rgb { r, g, b } => color::Rgb(r, g, b).fg_string().into(),
     }
 }
@@ -215,12 +277,33 @@ fn get_ansi_fg(color: AnsiColor, mode: AnsiMode) -> &'static str {
     }
 }
 
+fn get_effect_off(effect: Effect) -> &'static str {
+    match effect {
+        // bold and dim share the same sequence for disabling them
+        Effect::Bold | Effect::Dim => style::NoFaint.as_ref(),
+        Effect::Italic => style::NoItalic.as_ref(),
+        Effect::Underline => style::NoUnderline.as_ref(),
+        Effect::Strikethrough => style::NoCrossedOut.as_ref(),
+        Effect::Reverse => style::NoInvert.as_ref(),
+        Effect::Blink => style::NoBlink.as_ref(),
+        // termion does not provide sequences for the hidden and overline effects
+        Effect::Hidden => "\x1b[28m",
+        Effect::Overline => "\x1b[55m",
+    }
+}
+
 fn get_effect(effect: Effect) -> &'static str {
     match effect {
         Effect::Bold => style::Bold.as_ref(),
         Effect::Italic => style::Italic.as_ref(),
         Effect::Underline => style::Underline.as_ref(),
         Effect::Strikethrough => style::CrossedOut.as_ref(),
+        Effect::Dim => style::Faint.as_ref(),
+        Effect::Reverse => style::Invert.as_ref(),
+        Effect::Blink => style::Blink.as_ref(),
+        // termion does not provide sequences for the hidden and overline effects
+        Effect::Hidden => "\x1b[8m",
+        Effect::Overline => "\x1b[53m",
     }
 }
 
@@ -239,6 +322,9 @@ pub fn render<'a>(mut w: impl io::Write, s: impl Into<StyledStr<'a>>) -> io::Res
 
 /// Renders multiple styled string to the given output using `termion`.
 ///
+/// This function only writes the escape sequences for the attributes that change between
+/// consecutive strings, or a reset if it is shorter, and resets the style after the last string.
+///
 /// # Example
 ///
 /// ```
@@ -257,8 +343,149 @@ where
     S: Into<StyledStr<'a>>,
     W: io::Write,
 {
+    let mut state = ansi::OutputState::default();
     for s in iter {
-        write!(w, "{}", s.into().termion())?;
+        state.render(&mut w, s.into(), write_transition)?;
+    }
+    state.finish(&mut w, write_transition)
+}
+
+/// Writes the escape sequences for the given style transition.
+fn write_transition(w: &mut impl io::Write, transition: Transition) -> io::Result<()> {
+    if transition.reset {
+        write!(w, "{}", style::Reset)?;
+    }
+    for change in transition.changes {
+        let sequence: borrow::Cow<'_, str> = match change {
+            Change::NormalIntensity => get_effect_off(Effect::Bold).into(),
+            Change::EffectOn(effect) => get_effect(effect).into(),
+            Change::EffectOff(effect) => get_effect_off(effect).into(),
+            Change::Underline(UnderlineStyle::Single) => get_effect(Effect::Underline).into(),
+            Change::Fg(color) => color.map_or_else(|| color::Reset.fg_str().into(), get_fg),
+            Change::Bg(color) => color.map_or_else(|| color::Reset.bg_str().into(), get_bg),
+            // termion does not support underline styles and colors
+            Change::Underline(_) | Change::UnderlineColor(_) => {
+                ansi::change_sequence(change).into()
+            }
+        };
+        w.write_all(sequence.as_bytes())?;
     }
     Ok(())
 }
+
+/// Renders a styled string to the given output using `termion`, converting it to the given
+/// terminal capabilities.
+///
+/// See [`Capabilities::apply`][] for more information.
+///
+/// # Example
+///
+/// ```
+/// use text_style::terminal::Capabilities;
+///
+/// let s = text_style::StyledStr::plain("test").bold();
+/// text_style::termion::render_with(std::io::stdout(), s, &Capabilities::from_env(false))
+///     .expect("Failed to render string");
+/// ```
+///
+/// [`Capabilities::apply`]: ../terminal/struct.Capabilities.html#method.apply
+pub fn render_with<'a>(
+    w: impl io::Write,
+    s: impl Into<StyledStr<'a>>,
+    capabilities: &Capabilities,
+) -> io::Result<()> {
+    render(w, capabilities.apply(s))
+}
+
+/// Renders multiple styled string to the given output using `termion`, converting them to the
+/// given terminal capabilities.
+///
+/// See [`Capabilities::apply`][] for more information.
+///
+/// # Example
+///
+/// ```
+/// use text_style::terminal::Capabilities;
+///
+/// let v = vec![
+///     text_style::StyledStr::plain("test").bold(),
+///     text_style::StyledStr::plain(" "),
+///     text_style::StyledStr::plain("test2").italic(),
+/// ];
+/// text_style::termion::render_iter_with(
+///     std::io::stdout(),
+///     v.iter(),
+///     &Capabilities::from_env(false),
+/// )
+/// .expect("Failed to render string");
+/// ```
+///
+/// [`Capabilities::apply`]: ../terminal/struct.Capabilities.html#method.apply
+pub fn render_iter_with<'a, I, Iter, S, W>(
+    w: W,
+    iter: I,
+    capabilities: &Capabilities,
+) -> io::Result<()>
+where
+    I: IntoIterator<Item = S, IntoIter = Iter>,
+    Iter: Iterator<Item = S>,
+    S: Into<StyledStr<'a>>,
+    W: io::Write,
+{
+    render_iter(w, iter.into_iter().map(|s| capabilities.apply(s)))
+}
+
+/// Renders a styled string to the given output using `termion`, resolving its semantic style with
+/// the given theme.
+///
+/// See [`Theme::apply`][] for more information.
+///
+/// # Example
+///
+/// ```
+/// use text_style::{theme::Theme, AnsiColor, Style, StyledStr};
+///
+/// let theme = Theme::new().with("error", Style::fg(AnsiColor::Red.light()));
+/// let s = StyledStr::themed("failed", "error");
+/// text_style::termion::render_themed(std::io::stdout(), s, &theme)
+///     .expect("Failed to render string");
+/// ```
+///
+/// [`Theme::apply`]: ../theme/struct.Theme.html#method.apply
+pub fn render_themed<'a>(
+    w: impl io::Write,
+    s: impl Into<StyledStr<'a>>,
+    theme: &Theme,
+) -> io::Result<()> {
+    render(w, theme.apply(s))
+}
+
+/// Renders multiple styled strings to the given output using `termion`, resolving their semantic
+/// styles with the given theme.
+///
+/// See [`Theme::apply`][] for more information.
+///
+/// # Example
+///
+/// ```
+/// use text_style::{theme::Theme, AnsiColor, Style, StyledStr};
+///
+/// let theme = Theme::new().with("error", Style::fg(AnsiColor::Red.light()));
+/// let v = vec![
+///     StyledStr::themed("error", "error"),
+///     StyledStr::plain(": failed"),
+/// ];
+/// text_style::termion::render_iter_themed(std::io::stdout(), v.iter(), &theme)
+///     .expect("Failed to render string");
+/// ```
+///
+/// [`Theme::apply`]: ../theme/struct.Theme.html#method.apply
+pub fn render_iter_themed<'a, I, Iter, S, W>(w: W, iter: I, theme: &Theme) -> io::Result<()>
+where
+    I: IntoIterator<Item = S, IntoIter = Iter>,
+    Iter: Iterator<Item = S>,
+    S: Into<StyledStr<'a>>,
+    W: io::Write,
+{
+    render_iter(w, iter.into_iter().map(|s| theme.apply(s)))
+}
@@ -0,0 +1,173 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+//! Named semantic styles.
+//!
+//! This module provides the [`Theme`][] struct that maps semantic names like `error` or `path` to
+//! styles.  Names can consist of multiple dot-separated segments.  If there is no style for a
+//! name, the first segment is removed and the lookup is repeated, so `diagnostic.error` falls back
+//! to `error`.
+//!
+//! Styled strings can refer to a semantic style using the [`StyledStr::themed`][] and
+//! [`StyledString::themed`][] constructors.  The `render_themed` and `render_iter_themed`
+//! functions of the `ansi`, `ansi_term`, `crossterm` and `termion` modules resolve these styles
+//! while rendering the strings.  All other conversion and render functions of this crate ignore
+//! the style names, so strings with semantic styles must be passed to [`Theme::apply`][],
+//! [`Theme::apply_string`][] or [`Theme::apply_text`][] before using them.
+//!
+//! # Example
+//!
+//! ```
+//! use text_style::{theme::Theme, AnsiColor, Effect, Style, StyledStr};
+//!
+//! let theme = Theme::new()
+//!     .with("error", Style::fg(AnsiColor::Red.light()))
+//!     .with("path", Style::effect(Effect::Underline));
+//!
+//! let s = StyledStr::themed("failed", "diagnostic.error");
+//! text_style::ansi::render_themed(std::io::stdout(), s, &theme)
+//!     .expect("Failed to render string");
+//! ```
+//!
+//! [`StyledStr::themed`]: ../struct.StyledStr.html#method.themed
+//! [`StyledString::themed`]: ../struct.StyledString.html#method.themed
+//! [`Theme`]: struct.Theme.html
+//! [`Theme::apply`]: struct.Theme.html#method.apply
+//! [`Theme::apply_string`]: struct.Theme.html#method.apply_string
+//! [`Theme::apply_text`]: struct.Theme.html#method.apply_text
+
+use std::collections;
+use std::iter;
+
+use crate::{Style, StyledStr, StyledString, StyledText};
+
+/// A mapping from semantic names to styles.
+///
+/// See the [module documentation](index.html) for more information.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Theme {
+    styles: collections::HashMap<String, Style>,
+}
+
+impl Theme {
+    /// Creates a new theme without any styles.
+    pub fn new() -> Theme {
+        Default::default()
+    }
+
+    /// Adds the given style with the given name to this theme.
+    pub fn with(mut self, name: impl Into<String>, style: Style) -> Self {
+        self.insert(name, style);
+        self
+    }
+
+    /// Adds the given style with the given name to this theme, returning the previous style with
+    /// this name (if set).
+    pub fn insert(&mut self, name: impl Into<String>, style: Style) -> Option<Style> {
+        self.styles.insert(name.into(), style)
+    }
+
+    /// Removes the style with the given name from this theme, returning the style (if set).
+    pub fn remove(&mut self, name: &str) -> Option<Style> {
+        self.styles.remove(name)
+    }
+
+    /// Returns the style with the given name, or, if it is not set, the style of the first
+    /// fallback name that is set.
+    ///
+    /// The fallback names are generated by removing the first dot-separated segment of the name.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use text_style::{theme::Theme, AnsiColor, Style};
+    ///
+    /// let theme = Theme::new().with("error", Style::fg(AnsiColor::Red.light()));
+    /// assert_eq!(theme.get("error"), Some(Style::fg(AnsiColor::Red.light())));
+    /// assert_eq!(theme.get("diagnostic.error"), Some(Style::fg(AnsiColor::Red.light())));
+    /// assert_eq!(theme.get("warning"), None);
+    /// ```
+    pub fn get(&self, name: &str) -> Option<Style> {
+        let mut name = name;
+        loop {
+            if let Some(style) = self.styles.get(name) {
+                return Some(*style);
+            }
+            match name.find('.') {
+                Some(idx) => name = &name[idx + 1..],
+                None => return None,
+            }
+        }
+    }
+
+    /// Returns the style with the given name, using the same fallback rules as [`get`][], or
+    /// the default style if there is no matching style.
+    ///
+    /// [`get`]: #method.get
+    pub fn resolve(&self, name: &str) -> Style {
+        self.get(name).unwrap_or_default()
+    }
+
+    /// Returns an iterator over the names and styles of this theme.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Style)> {
+        self.styles
+            .iter()
+            .map(|(name, style)| (name.as_str(), *style))
+    }
+
+    /// Resolves the semantic style of the given string.
+    ///
+    /// If the string has a [`style_name`][] and the theme contains a matching style, the style of
+    /// the string is combined with the semantic style.  The style of the string takes precedence,
+    /// see [`Style::and`][].
+    ///
+    /// [`style_name`]: ../struct.StyledStr.html#structfield.style_name
+    /// [`Style::and`]: ../struct.Style.html#method.and
+    pub fn apply<'a>(&self, s: impl Into<StyledStr<'a>>) -> StyledStr<'a> {
+        let mut s = s.into();
+        s.style = self.apply_style(s.style_name, s.style);
+        s
+    }
+
+    /// Resolves the semantic style of the given string.
+    ///
+    /// See [`apply`][] for more information.
+    ///
+    /// [`apply`]: #method.apply
+    pub fn apply_string(&self, mut s: StyledString) -> StyledString {
+        s.style = self.apply_style(s.style_name.as_deref(), s.style);
+        s
+    }
+
+    /// Resolves the semantic styles of the given text.
+    ///
+    /// See [`apply`][] for more information.
+    ///
+    /// [`apply`]: #method.apply
+    pub fn apply_text(&self, text: StyledText) -> StyledText {
+        text.into_iter().map(|s| self.apply_string(s)).collect()
+    }
+
+    fn apply_style(&self, name: Option<&str>, style: Option<Style>) -> Option<Style> {
+        match name.and_then(|name| self.get(name)) {
+            Some(theme_style) => Some(style.map_or(theme_style, |style| theme_style.and(style))),
+            None => style,
+        }
+    }
+}
+
+impl<S: Into<String>> iter::Extend<(S, Style)> for Theme {
+    fn extend<I: IntoIterator<Item = (S, Style)>>(&mut self, iter: I) {
+        for (name, style) in iter {
+            self.insert(name, style);
+        }
+    }
+}
+
+impl<S: Into<String>> iter::FromIterator<(S, Style)> for Theme {
+    fn from_iter<I: IntoIterator<Item = (S, Style)>>(iter: I) -> Theme {
+        let mut theme = Theme::new();
+        theme.extend(iter);
+        theme
+    }
+}
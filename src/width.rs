@@ -0,0 +1,226 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+//! Display width measurement and truncation.
+//!
+//! The number of bytes or characters of a string does not match the number of columns that it
+//! occupies in a terminal:  East Asian wide characters and most emoji occupy two columns, while
+//! combining characters and other zero-width characters do not occupy any column.  This module
+//! provides functions that measure the display width of strings and styled strings, truncate
+//! them to a maximum width and expand tabs.
+//!
+//! The width of a string is the sum of the widths of its grapheme clusters, and the width of a
+//! grapheme cluster is the maximum width of its characters.  Control characters have the width
+//! zero.
+//!
+//! # Example
+//!
+//! ```
+//! use text_style::{width, StyledStr};
+//!
+//! assert_eq!(width::str_width("abc"), 3);
+//! assert_eq!(width::str_width("日本"), 4);
+//! assert_eq!(width::str_width("e\u{301}"), 1);
+//!
+//! let v = vec![StyledStr::plain("日本").bold(), StyledStr::plain("語です")];
+//! assert_eq!(width::width(v.iter()), 10);
+//! let text = width::truncate(v.iter(), 7, "…");
+//! assert_eq!(text.plain_text(), "日本語…");
+//! assert_eq!(text.width(), 7);
+//! ```
+
+use unicode_segmentation::UnicodeSegmentation as _;
+use unicode_width::UnicodeWidthChar as _;
+
+use crate::{StyledStr, StyledString, StyledText};
+
+/// Returns the display width of the given string.
+///
+/// See the [module documentation](index.html) for more information.
+pub fn str_width(s: &str) -> usize {
+    s.graphemes(true).map(grapheme_width).sum()
+}
+
+/// Returns the display width of the given styled strings.
+///
+/// See the [module documentation](index.html) for more information.
+pub fn width<'a, I, Iter, S>(iter: I) -> usize
+where
+    I: IntoIterator<Item = S, IntoIter = Iter>,
+    Iter: Iterator<Item = S>,
+    S: Into<StyledStr<'a>>,
+{
+    iter.into_iter().map(|s| str_width(s.into().s)).sum()
+}
+
+/// Truncates the given styled strings to the given maximum display width.
+///
+/// If the strings are wider than `max_width`, as many grapheme clusters as possible are retained
+/// and the ellipsis is appended so that the result is not wider than `max_width`.  The retained
+/// text keeps its styles.  If the ellipsis does not have a style, it uses the style of the string
+/// that was cut off.  An empty string can be used as the ellipsis to cut the strings without a
+/// marker.
+///
+/// # Example
+///
+/// ```
+/// use text_style::{width, StyledStr, StyledString};
+///
+/// let v = vec![StyledStr::plain("abc").bold(), StyledStr::plain("def").italic()];
+/// assert_eq!(width::truncate(v.iter(), 6, "…").spans(), &[
+///     StyledString::plain("abc".to_owned()).bold(),
+///     StyledString::plain("def".to_owned()).italic(),
+/// ]);
+/// assert_eq!(width::truncate(v.iter(), 5, "…").spans(), &[
+///     StyledString::plain("abc".to_owned()).bold(),
+///     StyledString::plain("d…".to_owned()).italic(),
+/// ]);
+/// assert_eq!(width::truncate(v.iter(), 3, StyledStr::plain("...").dim()).spans(), &[
+///     StyledString::plain("...".to_owned()).dim(),
+/// ]);
+/// ```
+pub fn truncate<'a, 'b, I, Iter, S>(
+    iter: I,
+    max_width: usize,
+    ellipsis: impl Into<StyledStr<'b>>,
+) -> StyledText
+where
+    I: IntoIterator<Item = S, IntoIter = Iter>,
+    Iter: Iterator<Item = S>,
+    S: Into<StyledStr<'a>>,
+{
+    let spans: Vec<StyledStr<'a>> = iter.into_iter().map(Into::into).collect();
+    if width(spans.iter()) <= max_width {
+        return spans.into_iter().collect();
+    }
+
+    let mut ellipsis = ellipsis.into();
+    let (ellipsis_str, ellipsis_width) = split_at_width(ellipsis.s, max_width);
+    ellipsis.s = ellipsis_str;
+    let mut available = max_width - ellipsis_width;
+
+    let mut text = StyledText::new();
+    for s in spans {
+        let (retained, retained_width) = split_at_width(s.s, available);
+        if retained.len() == s.s.len() {
+            available -= retained_width;
+            text.push(s);
+            continue;
+        }
+
+        if !retained.is_empty() {
+            let mut retained_s = s.clone();
+            retained_s.s = retained;
+            text.push(retained_s);
+        }
+        if ellipsis.style.is_none() {
+            ellipsis.style = s.style;
+        }
+        break;
+    }
+    push_merged(&mut text, ellipsis.into());
+    text
+}
+
+/// Replaces the tabs in the given string with spaces up to the next tab stop.
+///
+/// The tab stops are placed every `tab_width` columns, and `column` is the display width of the
+/// text before the string on the same line.  Newlines reset the column.  If `tab_width` is zero,
+/// tabs are removed.
+///
+/// # Example
+///
+/// ```
+/// use text_style::width;
+///
+/// assert_eq!(width::expand_tabs("a\tbc\td", 4, 0), "a   bc  d");
+/// assert_eq!(width::expand_tabs("日\tb\n\tc", 4, 1), "日 b\n    c");
+/// assert_eq!(width::expand_tabs("a\tb", 0, 0), "ab");
+/// ```
+pub fn expand_tabs(s: &str, tab_width: usize, column: usize) -> String {
+    let mut column = column;
+    let mut result = String::with_capacity(s.len());
+    for grapheme in s.graphemes(true) {
+        match grapheme {
+            "\t" => {
+                if tab_width > 0 {
+                    let n = tab_width - column % tab_width;
+                    result.push_str(&" ".repeat(n));
+                    column += n;
+                }
+            }
+            "\n" | "\r\n" => {
+                result.push_str(grapheme);
+                column = 0;
+            }
+            _ => {
+                result.push_str(grapheme);
+                column += grapheme_width(grapheme);
+            }
+        }
+    }
+    result
+}
+
+/// Returns the display width of the given grapheme cluster.
+pub(crate) fn grapheme_width(grapheme: &str) -> usize {
+    grapheme
+        .chars()
+        .map(|c| c.width().unwrap_or_default())
+        .max()
+        .unwrap_or_default()
+}
+
+/// Returns the longest prefix of the given string that is not wider than `max_width`, and the
+/// width of the prefix.
+pub(crate) fn split_at_width(s: &str, max_width: usize) -> (&str, usize) {
+    let mut width = 0;
+    for (idx, grapheme) in s.grapheme_indices(true) {
+        let grapheme_width = grapheme_width(grapheme);
+        if width + grapheme_width > max_width {
+            return (&s[..idx], width);
+        }
+        width += grapheme_width;
+    }
+    (s, width)
+}
+
+/// Appends the given string to the text, merging it with the last span if the style and the link
+/// are the same.
+pub(crate) fn push_merged(text: &mut StyledText, s: StyledString) {
+    if s.s.is_empty() {
+        return;
+    }
+    match text.spans_mut().last_mut() {
+        Some(last)
+            if last.style == s.style && last.link == s.link && last.style_name == s.style_name =>
+        {
+            last.s.push_str(&s.s);
+        }
+        _ => text.push(s),
+    }
+}
+
+impl<'a> StyledStr<'a> {
+    /// Returns the display width of this string, see the [`width`](width/index.html) module.
+    pub fn width(&self) -> usize {
+        str_width(self.s)
+    }
+}
+
+impl StyledString {
+    /// Returns the display width of this string, see the [`width`](width/index.html) module.
+    pub fn width(&self) -> usize {
+        str_width(&self.s)
+    }
+}
+
+impl StyledText {
+    /// Returns the display width of this text, see the [`width`](width/index.html) module.
+    ///
+    /// Newlines are not taken into account, so for text with multiple lines, this is the sum of
+    /// the widths of the lines.
+    pub fn width(&self) -> usize {
+        self.iter().map(StyledString::width).sum()
+    }
+}
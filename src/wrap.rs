@@ -0,0 +1,303 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+//! Word wrapping for styled text.
+//!
+//! This module provides the [`wrap`][] function that splits a sequence of styled strings into
+//! lines that are not wider than a given width, see the [`width`][] module.  The strings are split
+//! at whitespace, and the styles and hyperlinks of the strings are kept across line breaks.  Words
+//! can span multiple styled strings.
+//!
+//! Words that are wider than the line are broken at grapheme cluster boundaries unless this is
+//! disabled with [`Options::break_words`][].  Newlines in the input start a new line, and
+//! whitespace at the start of these lines is preserved.  All other whitespace at the start or the
+//! end of a line is removed.
+//!
+//! The first line and the following lines can be indented with different, optionally styled,
+//! strings, see [`Options::initial_indent`][] and [`Options::subsequent_indent`][].
+//!
+//! # Example
+//!
+//! ```
+//! use text_style::{wrap, StyledStr};
+//!
+//! let v = vec![
+//!     StyledStr::plain("error:").bold(),
+//!     StyledStr::plain(" the file could not be found"),
+//! ];
+//! let options = wrap::Options::new(16).subsequent_indent("       ");
+//! let lines = wrap::wrap(v.iter(), &options);
+//! for line in &lines {
+//!     text_style::ansi::render_iter(std::io::stdout(), line.iter())
+//!         .expect("Failed to render line");
+//!     println!();
+//! }
+//!
+//! let lines: Vec<_> = lines.iter().map(|line| line.plain_text()).collect();
+//! assert_eq!(lines, vec!["error: the file", "       could not", "       be found"]);
+//! ```
+//!
+//! [`Options::break_words`]: struct.Options.html#method.break_words
+//! [`Options::initial_indent`]: struct.Options.html#method.initial_indent
+//! [`Options::subsequent_indent`]: struct.Options.html#method.subsequent_indent
+//! [`width`]: ../width/index.html
+//! [`wrap`]: fn.wrap.html
+
+use std::mem;
+
+use unicode_segmentation::UnicodeSegmentation as _;
+
+use crate::width::{self, push_merged, split_at_width};
+use crate::{StyledStr, StyledString, StyledText};
+
+/// Options for the [`wrap`][] function.
+///
+/// [`wrap`]: fn.wrap.html
+#[derive(Clone, Debug, PartialEq)]
+pub struct Options {
+    /// The maximum width of the lines.
+    pub width: usize,
+    /// The indentation of the first line.
+    pub initial_indent: StyledString,
+    /// The indentation of all lines but the first line.
+    pub subsequent_indent: StyledString,
+    /// Whether words that are wider than the line should be broken.
+    pub break_words: bool,
+}
+
+impl Options {
+    /// Creates new options for the given line width without indentation that break long words.
+    pub fn new(width: usize) -> Options {
+        Options {
+            width,
+            initial_indent: Default::default(),
+            subsequent_indent: Default::default(),
+            break_words: true,
+        }
+    }
+
+    /// Sets the indentation of the first line.
+    pub fn initial_indent<'a>(mut self, indent: impl Into<StyledStr<'a>>) -> Self {
+        self.initial_indent = indent.into().into();
+        self
+    }
+
+    /// Sets the indentation of all lines but the first line.
+    pub fn subsequent_indent<'a>(mut self, indent: impl Into<StyledStr<'a>>) -> Self {
+        self.subsequent_indent = indent.into().into();
+        self
+    }
+
+    /// Sets whether words that are wider than the line should be broken.
+    ///
+    /// If this is disabled, words that are wider than the line are placed on a separate line that
+    /// is wider than the maximum width.
+    pub fn break_words(mut self, break_words: bool) -> Self {
+        self.break_words = break_words;
+        self
+    }
+}
+
+/// A word or a sequence of whitespace that can span multiple styled strings.
+#[derive(Clone, Debug)]
+pub(crate) enum Token<'a> {
+    Word(Vec<StyledStr<'a>>),
+    Space(Vec<StyledStr<'a>>),
+    Newline,
+}
+
+/// The state of the line wrapping algorithm.
+struct Wrapper<'a, 'o> {
+    options: &'o Options,
+    lines: Vec<StyledText>,
+    line: StyledText,
+    line_width: usize,
+    has_content: bool,
+    is_paragraph_start: bool,
+    space: Vec<StyledStr<'a>>,
+}
+
+impl<'a, 'o> Wrapper<'a, 'o> {
+    fn new(options: &'o Options) -> Self {
+        let mut wrapper = Wrapper {
+            options,
+            lines: Vec::new(),
+            line: StyledText::new(),
+            line_width: 0,
+            has_content: false,
+            is_paragraph_start: true,
+            space: Vec::new(),
+        };
+        wrapper.start_line();
+        wrapper
+    }
+
+    fn start_line(&mut self) {
+        let indent = if self.lines.is_empty() {
+            &self.options.initial_indent
+        } else {
+            &self.options.subsequent_indent
+        };
+        self.line = StyledText::new();
+        push_merged(&mut self.line, indent.clone());
+        self.line_width = indent.width();
+        self.has_content = false;
+        self.space.clear();
+    }
+
+    fn finish_line(&mut self) {
+        let line = mem::take(&mut self.line);
+        self.lines.push(line);
+        self.start_line();
+    }
+
+    fn push(&mut self, s: StyledStr<'_>) {
+        self.line_width += s.width();
+        push_merged(&mut self.line, s.into());
+    }
+
+    fn handle(&mut self, token: Token<'a>) {
+        match token {
+            Token::Newline => {
+                self.finish_line();
+                self.is_paragraph_start = true;
+            }
+            Token::Space(fragments) => {
+                if self.has_content {
+                    self.space = fragments;
+                } else if self.is_paragraph_start {
+                    for fragment in fragments {
+                        self.push(fragment);
+                    }
+                }
+            }
+            Token::Word(fragments) => {
+                self.push_word(fragments);
+                self.is_paragraph_start = false;
+            }
+        }
+    }
+
+    fn push_word(&mut self, fragments: Vec<StyledStr<'a>>) {
+        let word_width = width::width(fragments.iter());
+        let space = mem::take(&mut self.space);
+        if self.has_content {
+            if self.line_width + width::width(space.iter()) + word_width > self.options.width {
+                self.finish_line();
+            } else {
+                for fragment in space {
+                    self.push(fragment);
+                }
+            }
+        }
+
+        if self.line_width + word_width <= self.options.width || !self.options.break_words {
+            for fragment in fragments {
+                self.push(fragment);
+            }
+            self.has_content = true;
+            return;
+        }
+
+        for fragment in fragments {
+            let mut rest = fragment.s;
+            while !rest.is_empty() {
+                let available = self.options.width.saturating_sub(self.line_width);
+                let (mut head, _) = split_at_width(rest, available);
+                if head.is_empty() {
+                    if self.has_content {
+                        self.finish_line();
+                        continue;
+                    }
+                    // not even a single grapheme cluster fits into the empty line
+                    head = rest.graphemes(true).next().unwrap_or(rest);
+                }
+                let mut part = fragment.clone();
+                part.s = head;
+                self.push(part);
+                self.has_content = true;
+                rest = &rest[head.len()..];
+            }
+        }
+    }
+
+    fn finish(mut self) -> Vec<StyledText> {
+        if self.has_content {
+            self.finish_line();
+        }
+        self.lines
+    }
+}
+
+/// Wraps the given styled strings into lines that are not wider than the given width.
+///
+/// The lines do not contain a trailing newline.  See the [module documentation](index.html) for
+/// more information.
+///
+/// # Example
+///
+/// ```
+/// use text_style::{wrap, StyledStr, StyledString};
+///
+/// let v = vec![StyledStr::plain("aaa b").bold(), StyledStr::plain("b c")];
+/// let lines = wrap::wrap(v.iter(), &wrap::Options::new(4).initial_indent("- "));
+/// assert_eq!(lines.len(), 3);
+/// assert_eq!(lines[0].spans(), &[
+///     StyledString::plain("- ".to_owned()),
+///     StyledString::plain("aa".to_owned()).bold(),
+/// ]);
+/// assert_eq!(lines[1].spans(), &[
+///     StyledString::plain("a b".to_owned()).bold(),
+///     StyledString::plain("b".to_owned()),
+/// ]);
+/// assert_eq!(lines[2].spans(), &[StyledString::plain("c".to_owned())]);
+/// ```
+pub fn wrap<'a, I, Iter, S>(iter: I, options: &Options) -> Vec<StyledText>
+where
+    I: IntoIterator<Item = S, IntoIter = Iter>,
+    Iter: Iterator<Item = S>,
+    S: Into<StyledStr<'a>>,
+{
+    let mut wrapper = Wrapper::new(options);
+    for token in tokenize(iter.into_iter().map(Into::into)) {
+        wrapper.handle(token);
+    }
+    wrapper.finish()
+}
+
+fn is_space(c: char) -> bool {
+    c.is_whitespace() && c != '\n' && c != '\u{a0}'
+}
+
+/// Splits the given strings into words, whitespace and newlines.
+///
+/// Non-breaking spaces are treated as part of a word.
+pub(crate) fn tokenize<'a>(iter: impl Iterator<Item = StyledStr<'a>>) -> Vec<Token<'a>> {
+    let mut tokens = Vec::new();
+    for s in iter {
+        let mut rest = s.s;
+        while let Some(c) = rest.chars().next() {
+            if c == '\n' {
+                tokens.push(Token::Newline);
+                rest = &rest[1..];
+                continue;
+            }
+
+            let space = is_space(c);
+            let len = rest
+                .find(|c: char| c == '\n' || is_space(c) != space)
+                .unwrap_or(rest.len());
+            let mut fragment = s.clone();
+            fragment.s = &rest[..len];
+            rest = &rest[len..];
+
+            match tokens.last_mut() {
+                Some(Token::Word(fragments)) if !space => fragments.push(fragment),
+                Some(Token::Space(fragments)) if space => fragments.push(fragment),
+                _ if space => tokens.push(Token::Space(vec![fragment])),
+                _ => tokens.push(Token::Word(vec![fragment])),
+            }
+        }
+    }
+    tokens
+}
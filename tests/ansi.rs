@@ -0,0 +1,251 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+use text_style::{
+    ansi, AnsiColor, Color, Effect, Hyperlink, Style, StyledStr, StyledString, StyledText,
+    UnderlineStyle,
+};
+
+fn text(spans: Vec<StyledStr<'_>>) -> StyledText {
+    spans.into_iter().collect()
+}
+
+#[test]
+fn plain() {
+    assert_eq!(ansi::parse("test"), text(vec![StyledStr::plain("test")]));
+    assert_eq!(ansi::parse(""), StyledText::new());
+}
+
+#[test]
+fn effects() {
+    assert_eq!(
+        ansi::parse("\x1b[1mbold\x1b[3m italic\x1b[22m\x1b[4;9mtest\x1b[m"),
+        text(vec![
+            StyledStr::plain("bold").bold(),
+            StyledStr::plain(" italic").bold().italic(),
+            StyledStr::plain("test")
+                .italic()
+                .underline()
+                .strikethrough(),
+        ])
+    );
+}
+
+#[test]
+fn colors() {
+    assert_eq!(
+        ansi::parse("\x1b[31;102mtest\x1b[39m test\x1b[0m"),
+        text(vec![
+            StyledStr::plain("test")
+                .with(AnsiColor::Red.dark())
+                .on(AnsiColor::Green.light()),
+            StyledStr::plain(" test").on(AnsiColor::Green.light()),
+        ])
+    );
+}
+
+#[test]
+fn extended_colors() {
+    let rgb = Color::Rgb { r: 1, g: 2, b: 3 };
+    let expected = text(vec![StyledStr::plain("test")
+        .with(AnsiColor::Blue.light())
+        .on(rgb)]);
+    assert_eq!(ansi::parse("\x1b[38;5;12;48;2;1;2;3mtest"), expected);
+    assert_eq!(ansi::parse("\x1b[38:5:12;48:2::1:2:3mtest"), expected);
+    assert_eq!(ansi::parse("\x1b[38:5:12;48:2:1:2:3mtest"), expected);
+    assert_eq!(
+        ansi::parse("\x1b[38;5;196mtest"),
+        text(vec![StyledStr::plain("test").with(Color::Indexed(196))])
+    );
+}
+
+#[test]
+fn unsupported() {
+    let input = "a\x1b[2Kb\x1b]0;title\x07c\x1b[1;21md";
+    assert_eq!(
+        ansi::parse(input),
+        text(vec![
+            StyledStr::plain("a"),
+            StyledStr::plain("b"),
+            StyledStr::plain("c"),
+            StyledStr::plain("d").bold(),
+        ])
+    );
+
+    let errors: Vec<_> = ansi::Parser::new(input)
+        .filter_map(Result::err)
+        .map(|err| (err.kind, err.offset, err.sequence))
+        .collect();
+    assert_eq!(
+        errors,
+        vec![
+            (
+                ansi::ErrorKind::UnsupportedSequence,
+                1,
+                "\x1b[2K".to_owned()
+            ),
+            (
+                ansi::ErrorKind::UnsupportedSequence,
+                6,
+                "\x1b]0;title\x07".to_owned()
+            ),
+            (
+                ansi::ErrorKind::UnsupportedParameter,
+                17,
+                "\x1b[1;21m".to_owned()
+            ),
+        ]
+    );
+
+    assert_eq!(ansi::try_parse(input).unwrap_err().offset, 1);
+}
+
+#[test]
+fn out_of_range_parameter() {
+    let input = "\x1b[3ma\x1b[1;99999mb";
+    assert_eq!(
+        ansi::parse(input),
+        text(vec![
+            StyledStr::plain("a").italic(),
+            StyledStr::plain("b").italic().bold(),
+        ])
+    );
+
+    let err = ansi::try_parse(input).unwrap_err();
+    assert_eq!(err.kind, ansi::ErrorKind::UnsupportedParameter);
+    assert_eq!(err.offset, 5);
+}
+
+#[test]
+fn incomplete() {
+    assert_eq!(
+        ansi::parse("test\x1b[1"),
+        text(vec![StyledStr::plain("test")])
+    );
+    assert_eq!(
+        ansi::parse("test\x1b"),
+        text(vec![StyledStr::plain("test")])
+    );
+}
+
+#[test]
+fn roundtrip() {
+    let input = vec![
+        StyledString::from(StyledStr::plain("test").bold()),
+        StyledString::from(StyledStr::plain(" ")),
+        StyledString::from(
+            StyledStr::plain("test2")
+                .with(AnsiColor::Cyan.light())
+                .on(Color::Rgb { r: 1, g: 2, b: 3 })
+                .italic(),
+        ),
+    ];
+    let mut output = Vec::new();
+    text_style::ansi_term::render_iter(&mut output, &input).expect("Failed to render string");
+    let output = String::from_utf8(output).expect("Output is invalid UTF-8");
+    assert_eq!(
+        ansi::try_parse(&output).expect("Failed to parse string"),
+        StyledText::from(input)
+    );
+}
+
+#[test]
+fn extended_effects() {
+    assert_eq!(
+        ansi::parse("\x1b[2;5;7;8;53mtest\x1b[22;25;27;28;55m test"),
+        text(vec![
+            StyledStr::plain("test")
+                .dim()
+                .blink()
+                .reverse()
+                .hidden()
+                .overline(),
+            StyledStr::plain(" test"),
+        ])
+    );
+}
+
+#[test]
+fn underline_style() {
+    assert_eq!(
+        ansi::parse("\x1b[4:3;58;5;208mtest\x1b[4:0;59m test\x1b[4;58:2::1:2:3mtest"),
+        text(vec![
+            StyledStr::plain("test")
+                .underline_style(UnderlineStyle::Curly)
+                .underline_color(Color::Indexed(208)),
+            StyledStr::plain(" test"),
+            StyledStr::plain("test")
+                .underline()
+                .underline_color(Color::Rgb { r: 1, g: 2, b: 3 }),
+        ])
+    );
+}
+
+#[test]
+fn hyperlinks() {
+    assert_eq!(
+        ansi::parse(
+            "\x1b]8;;https://example.com\x1b\\test\x1b]8;;\x1b\\ \x1b]8;id=1;file:///tmp\x07test"
+        ),
+        text(vec![
+            StyledStr::plain("test").link("https://example.com"),
+            StyledStr::plain(" "),
+            StyledStr::plain("test").link(Hyperlink::new("file:///tmp").with_id("1")),
+        ])
+    );
+}
+
+#[test]
+fn render() {
+    let input = vec![
+        StyledStr::plain("a").bold().with(AnsiColor::Red.dark()),
+        StyledStr::plain("b").italic().with(AnsiColor::Red.dark()),
+        StyledStr::plain("c")
+            .italic()
+            .underline_style(UnderlineStyle::Curly)
+            .on(Color::Indexed(208)),
+        StyledStr::plain("d")
+            .underline()
+            .link("https://example.com"),
+        StyledStr::plain("e").link("https://example.com"),
+    ];
+    let mut output = Vec::new();
+    ansi::render_iter(&mut output, &input).expect("Failed to render string");
+    let output = String::from_utf8(output).expect("Output is invalid UTF-8");
+    assert_eq!(
+        output,
+        "\x1b[1;31ma\x1b[22;3mb\x1b[4:3;39;48;5;208mc\
+         \x1b]8;;https://example.com\x1b\\\x1b[0;4md\x1b[0me\x1b]8;;\x1b\\"
+    );
+    assert_eq!(
+        ansi::try_parse(&output).expect("Failed to parse string"),
+        text(input)
+    );
+}
+
+#[test]
+fn render_hidden_attributes() {
+    let mut style = Style::effect(Effect::Bold);
+    style.underline_style = Some(UnderlineStyle::Curly);
+    let input = [
+        StyledStr::plain("a").bold(),
+        StyledStr::styled("b", style),
+        StyledStr::plain("c"),
+    ];
+    let mut output = Vec::new();
+    ansi::render_iter(&mut output, &input).expect("Failed to render string");
+    let output = String::from_utf8(output).expect("Output is invalid UTF-8");
+    assert_eq!(output, "\x1b[1mab\x1b[0mc");
+}
+
+#[test]
+fn render_hyperlink_escapes() {
+    let link = Hyperlink::new("https://example.com/\x1b]0;title\x07\u{9b}").with_id("a;b:c\x07");
+    let mut output = Vec::new();
+    ansi::render(&mut output, StyledStr::plain("x").link(link)).expect("Failed to render string");
+    let output = String::from_utf8(output).expect("Output is invalid UTF-8");
+    assert_eq!(
+        output,
+        "\x1b]8;id=abc;https://example.com/%1B]0;title%07%C2%9B\x1b\\x\x1b]8;;\x1b\\"
+    );
+}
@@ -0,0 +1,99 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+use text_style::{AnsiColor, Color, ColorDepth, Style, StyledStr, StyledText};
+
+fn rgb(r: u8, g: u8, b: u8) -> Color {
+    Color::Rgb { r, g, b }
+}
+
+#[test]
+fn true_color() {
+    for color in &[rgb(1, 2, 3), Color::Indexed(100), AnsiColor::Red.dark()] {
+        assert_eq!(color.downgrade(ColorDepth::TrueColor), Some(*color));
+    }
+}
+
+#[test]
+fn indexed() {
+    assert_eq!(
+        rgb(0, 0, 0).downgrade(ColorDepth::Indexed),
+        Some(Color::Indexed(16))
+    );
+    assert_eq!(
+        rgb(255, 255, 255).downgrade(ColorDepth::Indexed),
+        Some(Color::Indexed(231))
+    );
+    assert_eq!(
+        rgb(95, 135, 175).downgrade(ColorDepth::Indexed),
+        Some(Color::Indexed(67))
+    );
+    assert_eq!(
+        rgb(128, 128, 128).downgrade(ColorDepth::Indexed),
+        Some(Color::Indexed(244))
+    );
+    assert_eq!(
+        rgb(30, 31, 29).downgrade(ColorDepth::Indexed),
+        Some(Color::Indexed(234))
+    );
+    assert_eq!(
+        AnsiColor::Green.light().downgrade(ColorDepth::Indexed),
+        Some(AnsiColor::Green.light())
+    );
+}
+
+#[test]
+fn ansi() {
+    assert_eq!(
+        rgb(200, 10, 20).downgrade(ColorDepth::Ansi),
+        Some(AnsiColor::Red.dark())
+    );
+    assert_eq!(
+        rgb(250, 250, 250).downgrade(ColorDepth::Ansi),
+        Some(AnsiColor::White.light())
+    );
+    assert_eq!(
+        rgb(10, 10, 10).downgrade(ColorDepth::Ansi),
+        Some(AnsiColor::Black.dark())
+    );
+    assert_eq!(
+        Color::Indexed(12).downgrade(ColorDepth::Ansi),
+        Some(AnsiColor::Blue.light())
+    );
+    assert_eq!(
+        Color::Indexed(51).downgrade(ColorDepth::Ansi),
+        Some(AnsiColor::Cyan.light())
+    );
+}
+
+#[test]
+fn no_color() {
+    assert_eq!(AnsiColor::Red.dark().downgrade(ColorDepth::NoColor), None);
+    assert_eq!(
+        StyledStr::plain("test")
+            .with(rgb(1, 2, 3))
+            .on(Color::Indexed(20))
+            .bold()
+            .downgrade(ColorDepth::NoColor),
+        StyledStr::plain("test").bold()
+    );
+}
+
+#[test]
+fn style() {
+    let style = Style::fg(rgb(255, 0, 0)).and(Style::bg(Color::Indexed(232)));
+    assert_eq!(
+        style.downgrade(ColorDepth::Ansi),
+        Style::fg(AnsiColor::Red.dark()).and(Style::bg(AnsiColor::Black.dark()))
+    );
+
+    let text: StyledText = vec![StyledStr::plain("a").with(rgb(0, 0, 255))]
+        .into_iter()
+        .collect();
+    assert_eq!(
+        text.downgrade(ColorDepth::Indexed),
+        vec![StyledStr::plain("a").with(Color::Indexed(21))]
+            .into_iter()
+            .collect()
+    );
+}
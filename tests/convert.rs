@@ -0,0 +1,119 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+use std::convert::TryFrom;
+
+use text_style::{AnsiColor, Color, Effect, Style, StyledStr, StyledString, StyledText};
+
+fn styles() -> Vec<Style> {
+    vec![
+        Style::default(),
+        Style::fg(AnsiColor::Red.dark()),
+        Style::bg(AnsiColor::Blue.light()),
+        Style::fg(Color::Indexed(208)).and(Style::bg(Color::Rgb { r: 1, g: 2, b: 3 })),
+        Style::effects(
+            vec![Effect::Bold, Effect::Italic, Effect::Underline]
+                .into_iter()
+                .collect(),
+        ),
+        Style::effects(
+            vec![Effect::Strikethrough, Effect::Reverse, Effect::Blink]
+                .into_iter()
+                .collect(),
+        ),
+    ]
+}
+
+#[test]
+fn ansi_term() {
+    for style in styles() {
+        assert_eq!(Style::from(ansi_term::Style::from(style)), style);
+    }
+    let mut all = Style::effects(vec![Effect::Dim, Effect::Hidden].into_iter().collect());
+    all.set_fg(AnsiColor::White.light());
+    assert_eq!(Style::from(ansi_term::Style::from(all)), all);
+
+    let s = ansi_term::Style::new().bold().paint("test");
+    assert_eq!(StyledStr::from(&s), StyledStr::plain("test").bold());
+    assert_eq!(
+        StyledString::from(s),
+        StyledString::plain("test".to_owned()).bold()
+    );
+    let s = ansi_term::Style::new().paint("test");
+    assert_eq!(StyledStr::from(&s), StyledStr::plain("test"));
+}
+
+#[test]
+fn crossterm() {
+    use crossterm::style;
+
+    for style in styles() {
+        assert_eq!(Style::try_from(style::ContentStyle::from(style)), Ok(style));
+    }
+
+    let mut content_style = style::ContentStyle::new();
+    content_style.foreground_color = Some(style::Color::Reset);
+    content_style.background_color = Some(style::Color::AnsiValue(9));
+    content_style.attributes.set(style::Attribute::RapidBlink);
+    content_style.attributes.set(style::Attribute::Framed);
+    content_style.attributes.set(style::Attribute::NoBold);
+    let err = Style::try_from(content_style).unwrap_err();
+    assert_eq!(err.unsupported, vec!["Framed", "NoBold"]);
+    assert_eq!(
+        err.value,
+        Style::bg(AnsiColor::Red.light()).and(Style::effect(Effect::Blink))
+    );
+    assert_eq!(err.to_string(), "Unsupported attributes: Framed, NoBold");
+
+    let content = style::StyledContent::new(style::ContentStyle::new(), 42);
+    assert_eq!(
+        StyledString::try_from(content),
+        Ok(StyledString::plain("42".to_owned()))
+    );
+}
+
+#[test]
+fn cursive() {
+    use cursive::{theme, utils::markup};
+
+    for style in styles() {
+        assert_eq!(Style::try_from(theme::Style::from(style)), Ok(style));
+    }
+
+    let text: StyledText = vec![
+        StyledString::plain("a".to_owned()),
+        StyledString::plain("b".to_owned()).with(AnsiColor::Green.dark()),
+    ]
+    .into();
+    let s = markup::StyledString::from(&text);
+    assert_eq!(StyledText::try_from(s), Ok(text));
+
+    let mut s = markup::StyledString::plain("a");
+    s.append_styled("b", theme::PaletteColor::Primary);
+    s.append_styled("c", theme::Color::RgbLowRes(5, 0, 0));
+    let err = StyledText::try_from(&s).unwrap_err();
+    assert_eq!(err.unsupported, vec!["Primary"]);
+    assert_eq!(
+        err.value.spans(),
+        &[
+            StyledString::plain("a".to_owned()),
+            StyledString::plain("b".to_owned()),
+            StyledString::plain("c".to_owned()).with(Color::Indexed(196)),
+        ]
+    );
+}
+
+#[test]
+fn cursive_low_res_color() {
+    use cursive::theme;
+
+    let color = |r, g, b| {
+        let style = theme::Style::from(theme::Color::RgbLowRes(r, g, b));
+        Style::try_from(style).map(|style| style.fg)
+    };
+    assert_eq!(color(0, 0, 0), Ok(Some(Color::Indexed(16))));
+    assert_eq!(color(1, 2, 3), Ok(Some(Color::Indexed(67))));
+    assert_eq!(color(5, 5, 5), Ok(Some(Color::Indexed(231))));
+    assert_eq!(color(6, 0, 9), Ok(Some(Color::Indexed(201))));
+    assert_eq!(color(255, 255, 255), Ok(Some(Color::Indexed(231))));
+}
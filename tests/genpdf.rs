@@ -0,0 +1,198 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use genpdf::fonts::{FontCache, FontData, FontFamily};
+use genpdf::{error, render, style, Element, Mm, RenderResult, Size};
+#[cfg(feature = "syntect-parsing")]
+use text_style::genpdf::CodeOptions;
+use text_style::genpdf::StyledParagraph;
+use text_style::StyledStr;
+
+const FONT_SIZE: u8 = 10;
+
+fn font_family(builtin: Option<printpdf::BuiltinFont>) -> FontFamily<FontData> {
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fonts/DejaVuSansMono.ttf"
+    );
+    let data = std::fs::read(path).expect("Failed to read font");
+    let font = FontData::new(data, builtin).expect("Failed to load font");
+    FontFamily {
+        regular: font.clone(),
+        bold: font.clone(),
+        italic: font.clone(),
+        bold_italic: font,
+    }
+}
+
+/// Returns the width of a character and the height of a line.
+fn cell_size(font_cache: &FontCache) -> (Mm, Mm) {
+    let style = style::Style::new().with_font_size(FONT_SIZE);
+    (
+        style.str_width(font_cache, "a"),
+        style.line_height(font_cache),
+    )
+}
+
+/// Returns how often `unit` fits into `length`, asserting that the result is an integer.
+fn count(length: Mm, unit: Mm) -> usize {
+    let epsilon = unit * 0.001;
+    (0..100)
+        .find(|n| {
+            let expected = unit * *n as f64;
+            length > expected - epsilon && length < expected + epsilon
+        })
+        .expect("Length is not a multiple of the unit")
+}
+
+/// An element that records the size of the rendered paragraph in characters and lines.
+struct Recorder {
+    paragraph: StyledParagraph,
+    sizes: Rc<RefCell<Vec<(usize, usize)>>>,
+}
+
+impl Element for Recorder {
+    fn render(
+        &mut self,
+        context: &genpdf::Context,
+        area: render::Area<'_>,
+        style: style::Style,
+    ) -> Result<RenderResult, error::Error> {
+        let result = self.paragraph.render(context, area, style)?;
+        let (char_width, line_height) = cell_size(&context.font_cache);
+        self.sizes.borrow_mut().push((
+            count(result.size.width, char_width),
+            count(result.size.height, line_height),
+        ));
+        Ok(result)
+    }
+}
+
+/// Renders the paragraph on pages that are `columns` characters wide and `rows` lines high and
+/// returns the size of the rendered text on each page.
+///
+/// The layout uses the metrics of the fixture font.  As embedding the font into the PDF document
+/// is slow in debug builds, the built-in Courier font is used for the output, so the text may only
+/// contain characters that are supported by the Windows-1252 encoding.
+fn sizes(paragraph: StyledParagraph, columns: f64, rows: f64) -> Vec<(usize, usize)> {
+    render_sizes(
+        paragraph,
+        columns,
+        rows,
+        Some(printpdf::BuiltinFont::Courier),
+    )
+}
+
+fn render_sizes(
+    paragraph: StyledParagraph,
+    columns: f64,
+    rows: f64,
+    builtin: Option<printpdf::BuiltinFont>,
+) -> Vec<(usize, usize)> {
+    let (char_width, line_height) = cell_size(&FontCache::new(font_family(builtin)));
+    let sizes = Rc::new(RefCell::new(Vec::new()));
+    let mut doc = genpdf::Document::new(font_family(builtin));
+    doc.set_font_size(FONT_SIZE);
+    doc.set_paper_size(Size::new(char_width * columns, line_height * rows));
+    doc.push(Recorder {
+        paragraph,
+        sizes: sizes.clone(),
+    });
+    doc.render(std::io::sink())
+        .expect("Failed to render document");
+    let sizes = sizes.borrow().clone();
+    sizes
+}
+
+#[test]
+fn words() {
+    let p = StyledParagraph::new("The quick brown fox");
+    assert_eq!(sizes(p, 10.5, 10.0), [(9, 2)]);
+    let p = StyledParagraph::new("a    b  c          d");
+    assert_eq!(sizes(p, 10.5, 10.0), [(9, 2)]);
+    let p = StyledParagraph::new("");
+    assert_eq!(sizes(p, 10.5, 10.0), [(0, 0)]);
+}
+
+#[test]
+fn newlines() {
+    let p = StyledParagraph::new("abc def\n\n  gh\n");
+    assert_eq!(sizes(p, 4.5, 10.0), [(4, 4)]);
+}
+
+#[test]
+fn break_words() {
+    let p = StyledParagraph::new("a abcdefghij b");
+    assert_eq!(sizes(p, 4.5, 10.0), [(4, 4)]);
+}
+
+#[test]
+fn non_breaking_space() {
+    // the non-breaking space is part of the word, so it is broken like a long word
+    let p = StyledParagraph::new("a\u{a0}b");
+    assert_eq!(render_sizes(p, 2.5, 10.0, None), [(2, 2)]);
+}
+
+#[test]
+fn styles() {
+    let v = [
+        StyledStr::plain("ab").bold(),
+        StyledStr::plain("c d").underline(),
+    ];
+    let p = v.iter().collect::<StyledParagraph>();
+    assert_eq!(sizes(p, 3.5, 10.0), [(3, 2)]);
+}
+
+#[test]
+fn page_breaks() {
+    let p = StyledParagraph::new("aaa bbb ccc ddd eee");
+    assert_eq!(sizes(p, 3.5, 2.0), [(3, 2), (3, 2), (3, 1)]);
+}
+
+#[cfg(feature = "syntect-parsing")]
+fn highlight(code: &str, options: &CodeOptions) -> Vec<u8> {
+    let ps = syntect::parsing::SyntaxSet::load_defaults_newlines();
+    let ts = syntect::highlighting::ThemeSet::load_defaults();
+    let syntax = ps.find_syntax_by_extension("rs").unwrap();
+    let doc = text_style::genpdf::highlight_document(
+        font_family(Some(printpdf::BuiltinFont::Courier)),
+        code,
+        &ps,
+        syntax,
+        &ts.themes["InspiredGitHub"],
+        options,
+    );
+    let mut output = Vec::new();
+    doc.render(&mut output).expect("Failed to render document");
+    output
+}
+
+#[cfg(feature = "syntect-parsing")]
+#[test]
+fn highlight_document() {
+    let code = "fn main() {\n\tprintln!(\"Hello, world!\");\n}\n";
+    let options = CodeOptions::new().title("main.rs");
+    let with_background = highlight(code, &options);
+    let without_background = highlight(code, &options.clone().background(false));
+    assert!(with_background.starts_with(b"%PDF"));
+    assert!(without_background.starts_with(b"%PDF"));
+    // the background is drawn as a path with about 1000 segments per page
+    assert!(without_background.len() < with_background.len());
+}
+
+#[cfg(feature = "syntect-parsing")]
+#[test]
+fn highlight_document_options() {
+    let code = "fn main() {}\n";
+    let options = CodeOptions::new()
+        .headers(false)
+        .line_numbers(false)
+        .tab_width(0)
+        .background(false);
+    assert!(highlight(code, &options).starts_with(b"%PDF"));
+    assert!(!CodeOptions::new().background(false).background);
+    assert!(CodeOptions::default().background);
+}
@@ -0,0 +1,222 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+use text_style::{
+    html, AnsiColor, AnsiMode, Color, Effect, Palette, StyledStr, StyledString, UnderlineStyle,
+};
+
+fn render(s: StyledStr<'_>, options: &html::Options) -> String {
+    let mut w = Vec::new();
+    html::render_with(&mut w, s, options).expect("Failed to render string");
+    String::from_utf8(w).expect("Invalid UTF-8")
+}
+
+#[test]
+fn plain() {
+    let options = html::Options::new();
+    assert_eq!(render(StyledStr::plain("a & b"), &options), "a &amp; b");
+    assert_eq!(render(StyledStr::plain("").bold(), &options), "");
+}
+
+#[test]
+fn inline() {
+    let options = html::Options::new();
+    let s = StyledStr::plain("test")
+        .with(Color::Rgb { r: 1, g: 2, b: 3 })
+        .on(AnsiColor::Blue.light())
+        .italic()
+        .underline()
+        .strikethrough()
+        .underline_style(UnderlineStyle::Curly)
+        .underline_color(Color::Indexed(196));
+    assert_eq!(
+        render(s, &options),
+        "<span style=\"color:#010203;background-color:#5555ff;font-style:italic;\
+         text-decoration-line:underline line-through;text-decoration-style:wavy;\
+         text-decoration-color:#ff0000\">test</span>"
+    );
+}
+
+#[test]
+fn reverse() {
+    let options = html::Options::new();
+    let s = StyledStr::plain("test")
+        .with(AnsiColor::Red.dark())
+        .reverse();
+    assert_eq!(
+        render(s, &options),
+        "<span style=\"background-color:#aa0000\">test</span>"
+    );
+}
+
+#[test]
+fn classes() {
+    let options = html::Options::new().classes("x-");
+    let s = StyledStr::plain("test")
+        .with(AnsiColor::Red.light())
+        .on(Color::Indexed(16))
+        .bold()
+        .underline()
+        .overline()
+        .underline_style(UnderlineStyle::Double);
+    assert_eq!(
+        render(s, &options),
+        "<span class=\"x-fg-bright-red x-bold x-underline x-overline x-double-underline\" \
+         style=\"background-color:#000000\">test</span>"
+    );
+
+    let stylesheet = options.stylesheet();
+    assert!(stylesheet.contains(".x-fg-bright-red { color: #ff5555; }\n"));
+    assert!(stylesheet.contains(".x-bg-black { background-color: #000000; }\n"));
+    assert!(stylesheet.contains(".x-bold { font-weight: bold; }\n"));
+    assert!(stylesheet
+        .contains(".x-underline.x-overline { text-decoration-line: underline overline; }\n"));
+    assert!(stylesheet.contains(".x-double-underline { text-decoration-style: double; }\n"));
+    assert_eq!(html::Options::new().stylesheet(), "");
+}
+
+#[test]
+fn palette() {
+    let mut palette = Palette::xterm();
+    palette.set(AnsiColor::Green, AnsiMode::Dark, (1, 2, 3));
+    let options = html::Options::new().palette(palette);
+    let s = StyledStr::plain("test")
+        .with(AnsiColor::Green.dark())
+        .on(Color::Indexed(1));
+    assert_eq!(
+        render(s, &options),
+        "<span style=\"color:#010203;background-color:#cd0000\">test</span>"
+    );
+}
+
+#[test]
+fn link() {
+    let options = html::Options::new();
+    let s = StyledStr::plain("docs").link("https://example.com/?a=1&b=2");
+    assert_eq!(
+        render(s, &options),
+        "<a href=\"https://example.com/?a=1&amp;b=2\">docs</a>"
+    );
+    let s = StyledStr::plain("docs").effect(Effect::Hidden).link("x");
+    assert_eq!(
+        render(s, &options),
+        "<a href=\"x\"><span style=\"visibility:hidden\">docs</span></a>"
+    );
+    for url in &[
+        "mailto:me@example.com",
+        "HTTP://example.com",
+        "/a:b",
+        "?a:b",
+        "#a:b",
+    ] {
+        let s = StyledStr::plain("docs").link(*url);
+        assert_eq!(render(s, &options), format!("<a href=\"{}\">docs</a>", url));
+    }
+    for url in &[
+        "javascript:alert(1)",
+        " JavaScript:alert(1)",
+        "java\tscript:alert(1)",
+        "data:text/html,<script>alert(1)</script>",
+        "vbscript:msgbox",
+    ] {
+        let s = StyledStr::plain("docs").bold().link(*url);
+        assert_eq!(
+            render(s, &options),
+            "<span style=\"font-weight:bold\">docs</span>"
+        );
+    }
+}
+
+#[test]
+fn render_iter() {
+    let v = [
+        StyledStr::plain("a").bold(),
+        StyledStr::plain(" "),
+        StyledStr::plain("b").dim(),
+    ];
+    let mut w = Vec::new();
+    html::render_iter(&mut w, v.iter()).expect("Failed to render strings");
+    assert_eq!(
+        String::from_utf8(w).unwrap(),
+        "<span style=\"font-weight:bold\">a</span> <span style=\"opacity:0.5\">b</span>"
+    );
+}
+
+#[test]
+fn parse_tags() {
+    let text = html::parse("<B>a<i>b</i></b><u>c</u><s>d</s><del>e</del><strong>f</strong>");
+    assert_eq!(
+        text.spans(),
+        &[
+            StyledString::plain("a".to_owned()).bold(),
+            StyledString::plain("b".to_owned()).bold().italic(),
+            StyledString::plain("c".to_owned()).underline(),
+            StyledString::plain("de".to_owned()).strikethrough(),
+            StyledString::plain("f".to_owned()).bold(),
+        ]
+    );
+}
+
+#[test]
+fn parse_colors() {
+    let text = html::parse(
+        "<font color=\"#f00\">a</font>\
+         <span style=\"color: RGB(1, 2, 3); background-color: navy\">b</span>\
+         <span style='font-weight: 700; text-decoration: underline overline'>c</span>\
+         <span style=\"color: nope\">d</span>",
+    );
+    assert_eq!(
+        text.spans(),
+        &[
+            StyledString::plain("a".to_owned()).with(Color::Rgb { r: 255, g: 0, b: 0 }),
+            StyledString::plain("b".to_owned())
+                .with(Color::Rgb { r: 1, g: 2, b: 3 })
+                .on(Color::Rgb { r: 0, g: 0, b: 128 }),
+            StyledString::plain("c".to_owned())
+                .bold()
+                .underline()
+                .overline(),
+            StyledString::plain("d".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn parse_lenient() {
+    let text = html::parse(
+        "<!DOCTYPE html><!-- x --><div class=\"a>b\">1 < 2 &amp;&unknown; 3<br/>\
+         <i>x</b>y</div>z</i><unclosed>&#65;&#x42;",
+    );
+    assert_eq!(
+        text.spans(),
+        &[
+            StyledString::plain("1 < 2 &&unknown; 3\n".to_owned()),
+            StyledString::plain("xy".to_owned()).italic(),
+            StyledString::plain("zAB".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn parse_links() {
+    let text = html::parse("<a href=\"https://example.com/?a=1&amp;b=2\">x <b>y</b></a>");
+    assert_eq!(
+        text.spans(),
+        &[
+            StyledString::plain("x ".to_owned()).link("https://example.com/?a=1&b=2"),
+            StyledString::plain("y".to_owned())
+                .bold()
+                .link("https://example.com/?a=1&b=2"),
+        ]
+    );
+}
+
+#[test]
+fn parse_render() {
+    let s = StyledStr::plain("<a & b>")
+        .with(Color::Rgb { r: 1, g: 2, b: 3 })
+        .bold()
+        .italic();
+    let rendered = render(s.clone(), &html::Options::new());
+    assert_eq!(html::parse(&rendered).spans(), &[StyledString::from(s)]);
+}
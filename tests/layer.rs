@@ -0,0 +1,63 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+use text_style::layer::{self, Setting, StyleLayer};
+use text_style::{AnsiColor, Color, Effect, Effects, Style, UnderlineStyle};
+
+#[test]
+fn inherit() {
+    let style = Style::fg(AnsiColor::Red.dark()).and(Style::effect(Effect::Bold));
+    assert_eq!(StyleLayer::new().apply(style), style);
+    assert_eq!(layer::resolve(&[StyleLayer::from(style)]), style);
+    assert_eq!(layer::resolve(&[]), Style::default());
+}
+
+#[test]
+fn unset() {
+    let parent = StyleLayer::from(Style::new(
+        Some(AnsiColor::Red.dark()),
+        Some(AnsiColor::White.light()),
+        Effects::only(Effect::Bold),
+    ));
+    let child = StyleLayer::new()
+        .fg(Setting::Unset)
+        .effect(Effect::Bold, false)
+        .effect(Effect::Italic, true);
+    assert_eq!(
+        layer::resolve(&[parent, child]),
+        Style::new(
+            None,
+            Some(AnsiColor::White.light()),
+            Effects::only(Effect::Italic)
+        )
+    );
+    assert_eq!(
+        parent.and(child).apply(Style::default()),
+        layer::resolve(&[parent, child])
+    );
+}
+
+#[test]
+fn effects() {
+    let mut layer = StyleLayer::from(Effect::Bold);
+    assert_eq!(layer.get_effect(Effect::Bold), Setting::Set(()));
+    layer.set_effect(Effect::Bold, false);
+    assert_eq!(layer.get_effect(Effect::Bold), Setting::Unset);
+    layer.inherit_effect(Effect::Bold);
+    assert_eq!(layer.get_effect(Effect::Bold), Setting::Inherit);
+    assert_eq!(layer, StyleLayer::new());
+}
+
+#[test]
+fn underline() {
+    let color = Color::Rgb { r: 1, g: 2, b: 3 };
+    let mut parent = StyleLayer::from(Effect::Underline);
+    parent.underline_style = Setting::Set(UnderlineStyle::Curly);
+    parent.underline_color = Setting::Set(color);
+    let mut child = StyleLayer::new();
+    child.underline_style = Setting::Unset;
+
+    let style = layer::resolve(&[parent, child]);
+    assert_eq!(style.get_underline_style(), Some(UnderlineStyle::Single));
+    assert_eq!(style.underline_color, Some(color));
+}
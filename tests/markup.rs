@@ -0,0 +1,109 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+use text_style::{markup, AnsiColor, Color, StyledStr, StyledText};
+
+fn text(spans: Vec<StyledStr<'_>>) -> StyledText {
+    spans.into_iter().collect()
+}
+
+#[test]
+fn plain() {
+    assert_eq!(
+        markup::parse("test").unwrap(),
+        text(vec![StyledStr::plain("test")])
+    );
+    assert_eq!(markup::parse("").unwrap(), StyledText::new());
+    assert_eq!(
+        markup::parse("a]b\\c").unwrap(),
+        text(vec![StyledStr::plain("a]b\\c")])
+    );
+}
+
+#[test]
+fn colors() {
+    assert_eq!(
+        markup::parse("[red]a[/][bright-cyan on 208]b[/][#ff8800 on rgb(1, 2,3)]c").unwrap(),
+        text(vec![
+            StyledStr::plain("a").with(AnsiColor::Red.dark()),
+            StyledStr::plain("b")
+                .with(AnsiColor::Cyan.light())
+                .on(Color::Indexed(208)),
+            StyledStr::plain("c")
+                .with(Color::Rgb {
+                    r: 255,
+                    g: 136,
+                    b: 0
+                })
+                .on(Color::Rgb { r: 1, g: 2, b: 3 }),
+        ])
+    );
+}
+
+#[test]
+fn nesting() {
+    assert_eq!(
+        markup::parse("[bold red]a[underline on white]b[/underline on white]c[/bold red]d")
+            .unwrap(),
+        text(vec![
+            StyledStr::plain("a").with(AnsiColor::Red.dark()).bold(),
+            StyledStr::plain("b")
+                .with(AnsiColor::Red.dark())
+                .on(AnsiColor::White.dark())
+                .bold()
+                .underline(),
+            StyledStr::plain("c").with(AnsiColor::Red.dark()).bold(),
+            StyledStr::plain("d"),
+        ])
+    );
+}
+
+#[test]
+fn escape() {
+    let input = "[x] \\ [/]";
+    let escaped = markup::escape(input);
+    assert_eq!(
+        markup::parse(&format!("[dim]{}[/]", escaped)).unwrap(),
+        text(vec![StyledStr::plain(input).dim()])
+    );
+}
+
+#[test]
+fn errors() {
+    let error = |s| {
+        let err = markup::parse(s).unwrap_err();
+        (err.kind, err.offset, err.text)
+    };
+    assert_eq!(
+        error("a [bold"),
+        (markup::ErrorKind::UnterminatedTag, 2, "[bold".to_owned())
+    );
+    assert_eq!(
+        error("a []"),
+        (markup::ErrorKind::InvalidStyle, 3, "".to_owned())
+    );
+    assert_eq!(
+        error("[bold on]"),
+        (markup::ErrorKind::InvalidStyle, 6, "on".to_owned())
+    );
+    assert_eq!(
+        error("[bold on #12345]"),
+        (markup::ErrorKind::InvalidStyle, 9, "#12345".to_owned())
+    );
+    assert_eq!(
+        error("a[/]"),
+        (markup::ErrorKind::UnexpectedClosingTag, 1, "[/]".to_owned())
+    );
+    assert_eq!(
+        error("[bold]a[/italic]"),
+        (
+            markup::ErrorKind::MismatchedClosingTag,
+            7,
+            "[/italic]".to_owned()
+        )
+    );
+    assert_eq!(
+        markup::parse("[bold]a[/italic]").unwrap_err().to_string(),
+        "Mismatched closing tag \"[/italic]\" at offset 7"
+    );
+}
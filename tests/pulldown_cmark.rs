@@ -0,0 +1,117 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+use text_style::pulldown_cmark::{self, Parser, Styles};
+use text_style::{AnsiColor, Style, StyledString, StyledText};
+
+fn plain(s: &str) -> StyledString {
+    StyledString::plain(s.to_owned())
+}
+
+#[test]
+fn inline() {
+    let text = pulldown_cmark::parse("*a* **b** ~~c~~ `d` [e](https://example.com)\nf");
+    assert_eq!(
+        text.spans(),
+        &[
+            plain("a").italic(),
+            plain(" "),
+            plain("b").bold(),
+            plain(" "),
+            plain("c").strikethrough(),
+            plain(" "),
+            plain("d").with(AnsiColor::Yellow.dark()),
+            plain(" "),
+            plain("e")
+                .with(AnsiColor::Blue.light())
+                .underline()
+                .link("https://example.com"),
+            plain(" f\n"),
+        ]
+    );
+}
+
+#[test]
+fn blocks() {
+    let styles = Styles {
+        headings: Default::default(),
+        block_quote: Style::default(),
+        list_marker: Style::default(),
+        ..Default::default()
+    };
+    let text = Parser::new()
+        .styles(styles)
+        .parse("# a\n\nb\n\n> c\n>\n> d\n\n- e\n  - f\n- g\n\n3. h\n4. i\n\n```\nj\n\nk\n```\n");
+    assert_eq!(
+        text.plain_text(),
+        "a\n\nb\n\n> c\n>\n> d\n\n• e\n  • f\n• g\n\n3. h\n4. i\n\n    j\n\n    k\n"
+    );
+}
+
+#[test]
+fn highlighter() {
+    let mut calls = Vec::new();
+    let text = Parser::new()
+        .highlighter(|lang, code| {
+            calls.push((lang.to_owned(), code.to_owned()));
+            if lang == "plain" {
+                None
+            } else {
+                Some(vec![plain(code).bold()].into_iter().collect::<StyledText>())
+            }
+        })
+        .parse("```rust,ignore\na\n```\n\n```plain\nb\n```\n\n```\nc\n```");
+    assert_eq!(
+        text.spans(),
+        &[
+            plain("    "),
+            plain("a").bold(),
+            plain("\n"),
+            plain("\n"),
+            plain("    "),
+            plain("b").with(AnsiColor::Yellow.dark()),
+            plain("\n"),
+            plain("\n"),
+            plain("    "),
+            plain("c").with(AnsiColor::Yellow.dark()),
+            plain("\n"),
+        ]
+    );
+    assert_eq!(
+        calls,
+        vec![
+            ("rust".to_owned(), "a\n".to_owned()),
+            ("plain".to_owned(), "b\n".to_owned())
+        ]
+    );
+}
+
+#[cfg(feature = "syntect-parsing")]
+#[test]
+fn syntect() {
+    use syntect::{highlighting, parsing};
+    use text_style::syntect::Converter;
+
+    let ps = parsing::SyntaxSet::load_defaults_newlines();
+    let ts = highlighting::ThemeSet::load_defaults();
+    let theme = &ts.themes["base16-ocean.dark"];
+
+    let text = Parser::new()
+        .syntect(&ps, theme, Converter::new().omit_theme_background(theme))
+        .parse("```rust\nfn main() {}\n```\n\n```unknown\nb\n```");
+    let spans = text.spans();
+    assert_eq!(spans[0], plain("    "));
+    assert_eq!(spans[1].s, "fn");
+    assert!(spans[1].style.is_some());
+    assert!(spans
+        .iter()
+        .all(|s| s.style.and_then(|style| style.bg).is_none()));
+    assert_eq!(
+        &spans[spans.len() - 3..],
+        &[
+            plain("    "),
+            plain("b").with(AnsiColor::Yellow.dark()),
+            plain("\n"),
+        ]
+    );
+}
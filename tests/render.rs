@@ -150,6 +150,117 @@ mod strikethrough {
     }
 }
 
+mod dim {
+    const OUTPUT: &[&'static str] = &["\x1b[2mtest\x1b[0m", "\x1b[2mtest\x1b[m"];
+
+    fn input() -> text_style::StyledStr<'static> {
+        text_style::StyledStr::plain("test").dim()
+    }
+
+    test_cases! { [input(); OUTPUT]
+        ansi_term,
+        crossterm,
+        termion,
+    }
+
+    #[test]
+    fn cursive() {
+        use cursive::utils::markup;
+
+        let input = input();
+        let output = markup::StyledString::styled("test", cursive::theme::Style::none());
+        assert_eq!(output, markup::StyledString::from(input));
+    }
+}
+
+mod reverse {
+    const OUTPUT: &[&'static str] = &["\x1b[7mtest\x1b[0m", "\x1b[7mtest\x1b[m"];
+
+    fn input() -> text_style::StyledStr<'static> {
+        text_style::StyledStr::plain("test").reverse()
+    }
+
+    test_cases! { [input(); OUTPUT]
+        ansi_term,
+        crossterm,
+        termion,
+    }
+
+    #[test]
+    fn cursive() {
+        use cursive::utils::markup;
+
+        let input = input();
+        let output = markup::StyledString::styled("test", cursive::theme::Effect::Reverse);
+        assert_eq!(output, markup::StyledString::from(input));
+    }
+}
+
+mod blink {
+    const OUTPUT: &[&'static str] = &["\x1b[5mtest\x1b[0m", "\x1b[5mtest\x1b[m"];
+
+    fn input() -> text_style::StyledStr<'static> {
+        text_style::StyledStr::plain("test").blink()
+    }
+
+    test_cases! { [input(); OUTPUT]
+        ansi_term,
+        crossterm,
+        termion,
+    }
+
+    #[test]
+    fn cursive() {
+        use cursive::utils::markup;
+
+        let input = input();
+        let output = markup::StyledString::styled("test", cursive::theme::Effect::Blink);
+        assert_eq!(output, markup::StyledString::from(input));
+    }
+}
+
+mod hidden {
+    const OUTPUT: &[&'static str] = &["\x1b[8mtest\x1b[0m", "\x1b[8mtest\x1b[m"];
+
+    fn input() -> text_style::StyledStr<'static> {
+        text_style::StyledStr::plain("test").hidden()
+    }
+
+    test_cases! { [input(); OUTPUT]
+        ansi_term,
+        crossterm,
+        termion,
+    }
+
+    #[test]
+    fn cursive() {
+        use cursive::utils::markup;
+
+        let input = input();
+        let output = markup::StyledString::styled("test", cursive::theme::Style::none());
+        assert_eq!(output, markup::StyledString::from(input));
+    }
+}
+
+mod overline {
+    const OUTPUT: &[&'static str] = &["\x1b[53mtest\x1b[0m", "\x1b[53mtest\x1b[m"];
+
+    fn input() -> text_style::StyledStr<'static> {
+        text_style::StyledStr::plain("test").overline()
+    }
+
+    test_cases! { [input(); OUTPUT]
+        crossterm,
+        termion,
+    }
+
+    #[test]
+    fn ansi_term() {
+        let output = crate::render(|v| text_style::ansi_term::render(v, input()));
+        crate::assert_output(&["test"], &output);
+    }
+}
+
 mod fg {
     const OUTPUT: &[&'static str] = &[
         "\x1b[31mtest\x1b[39m",
@@ -231,3 +342,213 @@ mod bg {
         assert_eq!(output, markup::StyledString::from(input));
     }
 }
+
+mod text {
+    const OUTPUT: &[&'static str] = &[
+        "\x1b[1mtest\x1b[0m \x1b[3mtest2\x1b[0m",
+        "\x1b[1mtest\x1b[0m \x1b[3mtest2\x1b[m",
+        "\x1b[1mtest\x1b[m \x1b[3mtest2\x1b[m",
+    ];
+
+    fn input() -> text_style::StyledText {
+        vec![
+            text_style::StyledStr::plain("test").bold(),
+            text_style::StyledStr::plain(" "),
+            text_style::StyledStr::plain("test2").italic(),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    macro_rules! test_cases {
+        ($( $name:ident, )+) => {
+            $(
+                #[test]
+                fn $name() {
+                    let input = input();
+                    let output = crate::render(|v| text_style::$name::render_iter(v, &input));
+                    crate::assert_output(OUTPUT, &output);
+                }
+             )*
+        };
+    }
+
+    test_cases! {
+        ansi_term,
+        crossterm,
+        termion,
+    }
+
+    #[test]
+    fn cursive() {
+        use cursive::{theme, utils::markup};
+
+        let mut output = markup::StyledString::styled("test", theme::Effect::Bold);
+        output.append_plain(" ");
+        output.append_styled("test2", theme::Effect::Italic);
+        assert_eq!(output, markup::StyledString::from(input()));
+    }
+
+    #[test]
+    fn plain_text() {
+        let input = input();
+        assert_eq!(input.len(), 3);
+        assert_eq!(input.plain_text(), "test test2");
+        assert_eq!(input.to_string(), "test test2");
+    }
+
+    #[test]
+    fn concat() {
+        let text = text_style::StyledText::from(text_style::StyledStr::plain("test").bold())
+            + text_style::StyledStr::plain(" ")
+            + text_style::StyledText::from(text_style::StyledStr::plain("test2").italic());
+        assert_eq!(text, input());
+    }
+}
+
+mod transitions {
+    fn input() -> Vec<text_style::StyledStr<'static>> {
+        use text_style::{AnsiColor, StyledStr, UnderlineStyle};
+
+        vec![
+            StyledStr::plain("a").bold().with(AnsiColor::Red.dark()),
+            StyledStr::plain("b")
+                .bold()
+                .italic()
+                .with(AnsiColor::Red.dark()),
+            StyledStr::plain("c")
+                .underline_style(UnderlineStyle::Curly)
+                .with(AnsiColor::Blue.dark()),
+            StyledStr::plain("d").link("https://example.com"),
+            StyledStr::plain("e").bold().link("https://example.com"),
+        ]
+    }
+
+    #[test]
+    fn crossterm() {
+        let output = crate::render(|v| text_style::crossterm::render_iter(v, input()));
+        crate::assert_output(
+            &["\x1b[1m\x1b[38;5;1ma\x1b[3mb\x1b[0m\x1b[4:3m\x1b[38;5;4mc\
+               \x1b]8;;https://example.com\x1b\\\x1b[0md\x1b[1me\x1b[0m\x1b]8;;\x1b\\"],
+            &output,
+        );
+    }
+
+    #[test]
+    fn termion() {
+        let output = crate::render(|v| text_style::termion::render_iter(v, input()));
+        crate::assert_output(
+            &["\x1b[1m\x1b[38;5;1ma\x1b[3mb\x1b[m\x1b[4:3m\x1b[38;5;4mc\
+               \x1b]8;;https://example.com\x1b\\\x1b[md\x1b[1me\x1b[m\x1b]8;;\x1b\\"],
+            &output,
+        );
+    }
+}
+
+mod indexed {
+    const OUTPUT: &[&'static str] = &[
+        "\x1b[38;5;208mtest\x1b[39m",
+        "\x1b[38;5;208mtest\x1b[0m",
+        "\x1b[38;5;208mtest\x1b[m",
+    ];
+
+    fn input() -> text_style::StyledStr<'static> {
+        text_style::StyledStr::plain("test").with(text_style::Color::Indexed(208))
+    }
+
+    test_cases! { [input(); OUTPUT]
+        ansi_term,
+        crossterm,
+        termion,
+    }
+
+    #[test]
+    fn cursive() {
+        use cursive::{theme, utils::markup};
+
+        let input = input();
+        let output = markup::StyledString::styled(
+            "test",
+            theme::ColorStyle::new(
+                theme::Color::RgbLowRes(5, 2, 0),
+                theme::ColorType::InheritParent,
+            ),
+        );
+        assert_eq!(output, markup::StyledString::from(input));
+    }
+
+    #[test]
+    fn genpdf() {
+        use genpdf::style;
+
+        let input = input();
+        let output = style::StyledStr::from(input.clone());
+        assert_eq!(output.s, input.s);
+        assert_eq!(output.style.color(), Some(style::Color::Rgb(255, 135, 0)));
+    }
+}
+
+mod underline_style {
+    const OUTPUT: &[&'static str] = &[
+        "\x1b[4:3m\x1b[58;5;9mtest\x1b[0m",
+        "\x1b[4:3m\x1b[58;5;9mtest\x1b[m",
+    ];
+
+    fn input() -> text_style::StyledStr<'static> {
+        text_style::StyledStr::plain("test")
+            .underline_style(text_style::UnderlineStyle::Curly)
+            .underline_color(text_style::AnsiColor::Red.light())
+    }
+
+    test_cases! { [input(); OUTPUT]
+        crossterm,
+        termion,
+    }
+
+    #[test]
+    fn ansi_term() {
+        let output = crate::render(|v| text_style::ansi_term::render(v, input()));
+        crate::assert_output(&["\x1b[4mtest\x1b[0m"], &output);
+    }
+}
+
+mod link {
+    const OUTPUT: &[&'static str] = &[
+        "\x1b]8;;https://example.com\x1b\\\x1b[1mtest\x1b[0m\x1b]8;;\x1b\\",
+        "\x1b]8;;https://example.com\x1b\\\x1b[1mtest\x1b[m\x1b]8;;\x1b\\",
+    ];
+
+    fn input() -> text_style::StyledStr<'static> {
+        text_style::StyledStr::plain("test")
+            .bold()
+            .link("https://example.com")
+    }
+
+    test_cases! { [input(); OUTPUT]
+        ansi_term,
+        crossterm,
+        termion,
+    }
+
+    #[test]
+    fn ansi_term_iter() {
+        let link = text_style::Hyperlink::new("https://example.com").with_id("1");
+        let input = vec![
+            text_style::StyledStr::plain("a").link(link.clone()),
+            text_style::StyledStr::plain("b").link(link),
+            text_style::StyledStr::plain("c"),
+        ];
+        let output = crate::render(|v| text_style::ansi_term::render_iter(v, &input));
+        crate::assert_output(
+            &["\x1b]8;id=1;https://example.com\x1b\\ab\x1b]8;;\x1b\\c"],
+            &output,
+        );
+    }
+
+    #[test]
+    fn render_with() {
+        let capabilities = text_style::terminal::Capabilities::none();
+        let output = crate::render(|v| text_style::termion::render_with(v, input(), &capabilities));
+        crate::assert_output(&["test"], &output);
+    }
+}
@@ -0,0 +1,93 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+use text_style::{AnsiColor, Color, Effect, Effects, ParseStyleError, Style, UnderlineStyle};
+
+fn parse(s: &str) -> Result<Style, (usize, String)> {
+    s.parse()
+        .map_err(|err: ParseStyleError| (err.offset, err.text))
+}
+
+#[test]
+fn parse_style() {
+    assert_eq!(parse(""), Ok(Style::default()));
+    assert_eq!(
+        parse("bold italic #ff8800 on 236"),
+        Ok(Style::new(
+            Some(Color::Rgb {
+                r: 255,
+                g: 136,
+                b: 0
+            }),
+            Some(Color::Indexed(236)),
+            vec![Effect::Bold, Effect::Italic].into_iter().collect(),
+        ))
+    );
+    assert_eq!(
+        parse("  red dim ul  "),
+        Ok(Style::new(
+            Some(AnsiColor::Red.dark()),
+            None,
+            vec![Effect::Dim, Effect::Underline].into_iter().collect(),
+        ))
+    );
+    assert_eq!(
+        parse("strike on rgb(1, 2, 3)"),
+        Ok(Style::new(
+            None,
+            Some(Color::Rgb { r: 1, g: 2, b: 3 }),
+            Effects::only(Effect::Strikethrough),
+        ))
+    );
+
+    let mut style = Style::effect(Effect::Underline);
+    style.set_underline_style(UnderlineStyle::Curly);
+    style.set_underline_color(AnsiColor::Blue.light());
+    assert_eq!(
+        parse("curly-underline underline-color bright-blue"),
+        Ok(style)
+    );
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(parse("bold purple"), Err((5, "purple".to_owned())));
+    assert_eq!(parse("red on"), Err((4, "on".to_owned())));
+    assert_eq!(parse("on 256"), Err((3, "256".to_owned())));
+    assert_eq!(parse("#12345g"), Err((0, "#12345g".to_owned())));
+    assert_eq!(parse("rgb(1,2)"), Err((0, "rgb(1,2)".to_owned())));
+    assert_eq!(
+        " bright-pink".parse::<Color>(),
+        Err(ParseStyleError {
+            offset: 1,
+            text: "bright-pink".to_owned()
+        })
+    );
+}
+
+#[test]
+fn roundtrip() {
+    for s in &[
+        "",
+        "bold",
+        "italic underline strikethrough dim reverse blink hidden overline",
+        "bright-white on black",
+        "dashed-underline #0a0b0c on 17 underline-color red",
+    ] {
+        let style = parse(s).expect("Failed to parse style");
+        assert_eq!(&style.to_string(), s);
+    }
+
+    for color in &[
+        AnsiColor::Magenta.dark(),
+        AnsiColor::Yellow.light(),
+        Color::Indexed(0),
+        Color::Rgb {
+            r: 0,
+            g: 128,
+            b: 255,
+        },
+    ] {
+        assert_eq!(color.to_string().parse(), Ok(*color));
+    }
+}
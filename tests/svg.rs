@@ -0,0 +1,136 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+use text_style::{svg, AnsiColor, Color, Palette, StyledStr};
+
+fn render(v: &[StyledStr<'_>], options: &svg::Options) -> String {
+    let mut w = Vec::new();
+    svg::render_iter_with(&mut w, v.iter(), options).expect("Failed to render image");
+    String::from_utf8(w).expect("Invalid UTF-8")
+}
+
+#[test]
+fn layout() {
+    let options = svg::Options::new().font("Fira Mono", 10.0).padding(0.0);
+    let v = [
+        StyledStr::plain("ab"),
+        StyledStr::plain("c\nd").bold(),
+        StyledStr::plain("\n\nefgh\n"),
+    ];
+    assert_eq!(
+        render(&v, &options),
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"24\" height=\"48\" \
+         viewBox=\"0 0 24 48\" font-family=\"Fira Mono\" font-size=\"10\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"#000000\"/>\n\
+         <text y=\"9\" fill=\"#aaaaaa\" xml:space=\"preserve\">\
+         <tspan x=\"0\">ab</tspan><tspan x=\"12\" font-weight=\"bold\">c</tspan></text>\n\
+         <text y=\"21\" fill=\"#aaaaaa\" xml:space=\"preserve\">\
+         <tspan x=\"0\" font-weight=\"bold\">d</tspan></text>\n\
+         <text y=\"45\" fill=\"#aaaaaa\" xml:space=\"preserve\">\
+         <tspan x=\"0\">efgh</tspan></text>\n\
+         </svg>\n"
+    );
+}
+
+#[test]
+fn colors() {
+    let mut palette = Palette::vga();
+    palette.set(AnsiColor::Blue, text_style::AnsiMode::Dark, (1, 2, 3));
+    let options = svg::Options::new()
+        .palette(palette)
+        .colors(
+            Color::Rgb {
+                r: 255,
+                g: 255,
+                b: 255,
+            },
+            AnsiColor::Blue.dark(),
+        )
+        .padding(0.0);
+    let v = [
+        StyledStr::plain("a").on(Color::Indexed(196)),
+        StyledStr::plain("b").with(AnsiColor::Red.dark()).reverse(),
+    ];
+    let svg = render(&v, &options);
+    assert!(svg.contains("<rect width=\"100%\" height=\"100%\" fill=\"#010203\"/>\n"));
+    assert!(
+        svg.contains("<rect x=\"0\" y=\"0\" width=\"8.4\" height=\"16.8\" fill=\"#ff0000\"/>\n")
+    );
+    assert!(
+        svg.contains("<rect x=\"8.4\" y=\"0\" width=\"8.4\" height=\"16.8\" fill=\"#aa0000\"/>\n")
+    );
+    assert!(svg.contains("<text y=\"12.6\" fill=\"#ffffff\" xml:space=\"preserve\">"));
+    assert!(svg.contains("<tspan x=\"0\">a</tspan><tspan x=\"8.4\" fill=\"#010203\">b</tspan>"));
+}
+
+#[test]
+fn effects() {
+    let options = svg::Options::new();
+    let v = [StyledStr::plain("<a>")
+        .italic()
+        .underline()
+        .strikethrough()
+        .dim()
+        .link("https://example.com")];
+    let svg = render(&v, &options);
+    assert!(svg.contains(
+        "<a href=\"https://example.com\"><tspan x=\"10\" font-style=\"italic\" \
+         text-decoration=\"underline line-through\" fill-opacity=\"0.5\">&lt;a&gt;</tspan></a>"
+    ));
+}
+
+#[test]
+fn empty() {
+    let options = svg::Options::new();
+    let svg = render(&[], &options);
+    assert_eq!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"20\" height=\"36.8\" \
+         viewBox=\"0 0 20 36.8\" font-family=\"monospace\" font-size=\"14\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"#000000\"/>\n\
+         </svg>\n"
+    );
+}
+
+#[test]
+fn wide_characters() {
+    let options = svg::Options::new().font("Fira Mono", 10.0).padding(0.0);
+    let v = [
+        StyledStr::plain("日本"),
+        StyledStr::plain("e\u{301}\x07\x1b[0m").bold(),
+        StyledStr::plain("x").link("javascript:alert(1)"),
+    ];
+    assert_eq!(
+        render(&v, &options),
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"54\" height=\"12\" \
+         viewBox=\"0 0 54 12\" font-family=\"Fira Mono\" font-size=\"10\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"#000000\"/>\n\
+         <text y=\"9\" fill=\"#aaaaaa\" xml:space=\"preserve\">\
+         <tspan x=\"0\">日本</tspan>\
+         <tspan x=\"24\" font-weight=\"bold\">e\u{301}[0m</tspan>\
+         <tspan x=\"48\">x</tspan></text>\n\
+         </svg>\n"
+    );
+}
+
+#[test]
+fn tabs() {
+    let options = svg::Options::new().font("Fira Mono", 10.0).padding(0.0);
+    let v = [
+        StyledStr::plain("ab\tc"),
+        StyledStr::plain("d\te\n").bold(),
+        StyledStr::plain("abcdefghij\tk"),
+    ];
+    assert_eq!(
+        render(&v, &options),
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"102\" height=\"24\" \
+         viewBox=\"0 0 102 24\" font-family=\"Fira Mono\" font-size=\"10\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"#000000\"/>\n\
+         <text y=\"9\" fill=\"#aaaaaa\" xml:space=\"preserve\">\
+         <tspan x=\"0\">ab      c</tspan>\
+         <tspan x=\"54\" font-weight=\"bold\">d      e</tspan></text>\n\
+         <text y=\"21\" fill=\"#aaaaaa\" xml:space=\"preserve\">\
+         <tspan x=\"0\">abcdefghij      k</tspan></text>\n\
+         </svg>\n"
+    );
+}
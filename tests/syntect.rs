@@ -0,0 +1,153 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+use syntect::highlighting::{self, FontStyle};
+use text_style::syntect::Converter;
+use text_style::{AnsiColor, Color, Effect, Style, StyledStr};
+
+fn color(r: u8, g: u8, b: u8, a: u8) -> highlighting::Color {
+    highlighting::Color { r, g, b, a }
+}
+
+fn rgb(r: u8, g: u8, b: u8) -> Color {
+    Color::Rgb { r, g, b }
+}
+
+fn style(
+    foreground: highlighting::Color,
+    background: highlighting::Color,
+    font_style: FontStyle,
+) -> highlighting::Style {
+    highlighting::Style {
+        foreground,
+        background,
+        font_style,
+    }
+}
+
+#[test]
+fn default() {
+    let s = style(
+        color(1, 2, 3, 0x80),
+        color(4, 5, 6, 0xff),
+        FontStyle::BOLD | FontStyle::UNDERLINE,
+    );
+    let expected = Style::fg(rgb(1, 2, 3))
+        .and(Style::bg(rgb(4, 5, 6)))
+        .and(Style::effect(Effect::Bold))
+        .and(Style::effect(Effect::Underline));
+    assert_eq!(Style::from(s), expected);
+    assert_eq!(Converter::new().style(s), expected);
+    assert_eq!(
+        Converter::new().styled_str((s, "test")),
+        StyledStr::from((s, "test"))
+    );
+}
+
+#[test]
+fn omit_background() {
+    let bg = color(4, 5, 6, 0xff);
+    let converter = Converter::new().omit_background(bg);
+    assert_eq!(
+        converter.style(style(color(1, 2, 3, 0xff), bg, FontStyle::ITALIC)),
+        Style::fg(rgb(1, 2, 3)).and(Style::effect(Effect::Italic))
+    );
+    assert_eq!(
+        converter.style(style(
+            color(1, 2, 3, 0xff),
+            color(4, 5, 7, 0xff),
+            FontStyle::empty()
+        )),
+        Style::fg(rgb(1, 2, 3)).and(Style::bg(rgb(4, 5, 7)))
+    );
+
+    let ts = highlighting::ThemeSet::load_defaults();
+    let theme = &ts.themes["base16-ocean.dark"];
+    let bg = theme.settings.background.unwrap();
+    let converter = Converter::new().omit_theme_background(theme);
+    assert_eq!(converter.omit_background, Some(bg));
+    assert_eq!(
+        converter.style(style(color(1, 2, 3, 0xff), bg, FontStyle::empty())),
+        Style::fg(rgb(1, 2, 3))
+    );
+}
+
+#[test]
+fn blend() {
+    let converter = Converter::new().blend(AnsiColor::White.light());
+    assert_eq!(
+        converter.style(style(
+            color(0, 0, 0, 0xff),
+            color(0, 0, 0, 0),
+            FontStyle::empty()
+        )),
+        Style::fg(rgb(0, 0, 0)).and(Style::bg(rgb(0xff, 0xff, 0xff)))
+    );
+    assert_eq!(
+        converter.style(style(
+            color(0, 0, 0, 0x80),
+            color(0, 0, 0xff, 0x80),
+            FontStyle::empty()
+        )),
+        Style::fg(rgb(0x3f, 0x3f, 0x7f)).and(Style::bg(rgb(0x7f, 0x7f, 0xff)))
+    );
+
+    let bg = color(0, 0, 0xff, 0x80);
+    let converter = converter.omit_background(bg);
+    assert_eq!(
+        converter.style(style(color(0, 0, 0, 0x80), bg, FontStyle::empty())),
+        Style::fg(rgb(0x3f, 0x3f, 0x7f))
+    );
+}
+
+#[cfg(feature = "syntect-parsing")]
+#[test]
+fn highlighted_lines() {
+    use syntect::parsing::SyntaxSet;
+    use text_style::syntect::HighlightedLines;
+    use text_style::StyledString;
+
+    let ps = SyntaxSet::load_defaults_newlines();
+    let ts = highlighting::ThemeSet::load_defaults();
+    let syntax = ps.find_syntax_plain_text();
+    let theme = &ts.themes["base16-ocean.dark"];
+    let s = "abc\n\ndef";
+
+    let lines: Vec<_> = HighlightedLines::new(&ps, syntax, theme, s).collect();
+    let texts: Vec<String> = lines
+        .iter()
+        .map(|line| line.iter().map(|s| s.s.as_str()).collect())
+        .collect();
+    assert_eq!(texts, ["abc\n", "\n", "def"]);
+
+    let converter = Converter::new().omit_theme_background(theme);
+    let fg = theme.settings.foreground.unwrap().into();
+    let lines: Vec<_> = HighlightedLines::new(&ps, syntax, theme, s)
+        .converter(converter)
+        .merge(true)
+        .collect();
+    assert_eq!(
+        lines,
+        [
+            vec![StyledString::plain("abc\n".to_owned()).with(fg)],
+            vec![StyledString::plain("\n".to_owned()).with(fg)],
+            vec![StyledString::plain("def".to_owned()).with(fg)],
+        ]
+    );
+
+    let syntax = ps.find_syntax_by_extension("rs").unwrap();
+    let s = "fn main() {\n    let x = [1, 2];\n}";
+    let lines: Vec<_> = HighlightedLines::new(&ps, syntax, theme, s).collect();
+    let merged: Vec<_> = HighlightedLines::new(&ps, syntax, theme, s)
+        .merge(true)
+        .collect();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(merged.len(), 3);
+    for (line, merged) in lines.iter().zip(merged.iter()) {
+        let text: String = line.iter().map(|s| s.s.as_str()).collect();
+        let merged_text: String = merged.iter().map(|s| s.s.as_str()).collect();
+        assert_eq!(text, merged_text);
+        assert!(merged.windows(2).all(|w| w[0].style != w[1].style));
+    }
+    assert!(lines[1].len() > merged[1].len());
+}
@@ -0,0 +1,142 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+use text_style::table::{Alignment, Border, Column, Overflow, Table};
+use text_style::{AnsiColor, Effect, Style, StyledStr, StyledString};
+
+fn plain_lines(table: &Table) -> Vec<String> {
+    table.lines().iter().map(|line| line.plain_text()).collect()
+}
+
+fn row<'a>(cells: &[&'a str]) -> Vec<StyledStr<'a>> {
+    cells.iter().map(|s| StyledStr::plain(s)).collect()
+}
+
+#[test]
+fn empty() {
+    assert_eq!(plain_lines(&Table::new()), Vec::<String>::new());
+    assert_eq!(
+        plain_lines(&Table::new().border(Border::ascii())),
+        vec!["++", "++"]
+    );
+}
+
+#[test]
+fn alignment() {
+    let table = Table::new()
+        .column(Column::new().align(Alignment::Left))
+        .column(Column::new().align(Alignment::Center))
+        .column(Column::new().align(Alignment::Right))
+        .row(row(&["a", "b", "c"]))
+        .row(row(&["aaaa", "bbbb", "cccc"]))
+        .row(row(&["日本", "日", "本"]));
+    assert_eq!(
+        plain_lines(&table),
+        vec!["a      b       c", "aaaa  bbbb  cccc", "日本   日     本",]
+    );
+}
+
+#[test]
+fn ragged_rows() {
+    let table = Table::new()
+        .padding(0)
+        .border(Border::ascii())
+        .header(row(&["a", "b"]))
+        .row(row(&["1"]))
+        .row(row(&["1", "2", "3"]));
+    assert_eq!(
+        plain_lines(&table),
+        vec!["+-+-+-+", "|a|b| |", "+-+-+-+", "|1| | |", "|1|2|3|", "+-+-+-+"]
+    );
+}
+
+#[test]
+fn widths() {
+    let table = Table::new()
+        .column(Column::new().min_width(3))
+        .column(Column::new().max_width(5))
+        .column(Column::new().max_width(5).overflow(Overflow::Truncate))
+        .border(Border::single())
+        .row(row(&["a", "abc defgh", "abcdefgh"]))
+        .row(row(&["b\nc", "d", "e\nfghijkl"]));
+    assert_eq!(
+        plain_lines(&table),
+        vec![
+            "┌─────┬───────┬───────┐",
+            "│ a   │ abc   │ abcd… │",
+            "│     │ defgh │       │",
+            "│ b   │ d     │ e     │",
+            "│ c   │       │ fghi… │",
+            "└─────┴───────┴───────┘",
+        ]
+    );
+}
+
+#[test]
+fn wide_graphemes() {
+    let table = Table::new()
+        .column(Column::new().max_width(1))
+        .column(Column::new().max_width(1).overflow(Overflow::Truncate))
+        .column(Column::new().max_width(0).align(Alignment::Right))
+        .border(Border::ascii())
+        .padding(0)
+        .row(row(&["日本", "日本", "a"]));
+    assert_eq!(
+        plain_lines(&table),
+        vec!["+-+-++", "|日|…|a|", "|本| ||", "+-+-++"]
+    );
+}
+
+#[test]
+fn styles() {
+    let red = Style::fg(AnsiColor::Red.dark());
+    let stripe = Style::bg(AnsiColor::Black.light());
+    let table = Table::new()
+        .header(row(&["h", "h"]))
+        .header_style(Style::effect(Effect::Bold))
+        .row(row(&["a", "b"]))
+        .row(vec![
+            StyledStr::plain("c").with(AnsiColor::Red.dark()),
+            "dd".into(),
+        ])
+        .stripes(stripe)
+        .border(Border::ascii())
+        .border_style(Style::effect(Effect::Dim))
+        .padding(0);
+    let lines = table.lines();
+    assert_eq!(lines.len(), 6);
+    assert_eq!(
+        lines[0].spans(),
+        &[StyledString::plain("+-+--+".to_owned()).dim()]
+    );
+    assert_eq!(
+        lines[1].spans(),
+        &[
+            StyledString::plain("|".to_owned()).dim(),
+            StyledString::plain("h".to_owned()).bold(),
+            StyledString::plain("|".to_owned()).dim(),
+            StyledString::plain("h ".to_owned()).bold(),
+            StyledString::plain("|".to_owned()).dim(),
+        ]
+    );
+    assert_eq!(
+        lines[3].spans(),
+        &[
+            StyledString::plain("|".to_owned()).dim(),
+            StyledString::plain("a".to_owned()),
+            StyledString::plain("|".to_owned()).dim(),
+            StyledString::plain("b ".to_owned()),
+            StyledString::plain("|".to_owned()).dim(),
+        ]
+    );
+    assert_eq!(
+        lines[4].spans(),
+        &[
+            StyledString::plain("|".to_owned()).dim(),
+            StyledString::styled("c".to_owned(), stripe.and(red)),
+            StyledString::plain("|".to_owned()).dim(),
+            StyledString::styled("dd".to_owned(), stripe),
+            StyledString::plain("|".to_owned()).dim(),
+        ]
+    );
+}
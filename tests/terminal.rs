@@ -0,0 +1,131 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+use text_style::terminal::Capabilities;
+use text_style::{AnsiColor, Color, ColorDepth, Effect, StyledStr};
+
+fn detect(is_tty: bool, vars: &[(&str, &str)]) -> Capabilities {
+    Capabilities::from_vars(is_tty, |name| {
+        vars.iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| (*value).to_owned())
+    })
+}
+
+#[test]
+fn color_depth() {
+    let depth = |vars| detect(true, vars).color_depth;
+    assert_eq!(depth(&[]), ColorDepth::Ansi);
+    assert_eq!(depth(&[("TERM", "xterm")]), ColorDepth::Ansi);
+    assert_eq!(depth(&[("TERM", "xterm-256color")]), ColorDepth::Indexed);
+    assert_eq!(depth(&[("TERM", "xterm-direct")]), ColorDepth::TrueColor);
+    assert_eq!(
+        depth(&[("TERM", "xterm-256color"), ("COLORTERM", "truecolor")]),
+        ColorDepth::TrueColor
+    );
+    assert_eq!(depth(&[("COLORTERM", "24bit")]), ColorDepth::TrueColor);
+}
+
+#[test]
+fn no_tty() {
+    assert_eq!(
+        detect(false, &[("TERM", "xterm-256color")]),
+        Capabilities::none()
+    );
+    assert_eq!(
+        detect(
+            false,
+            &[("TERM", "xterm-256color"), ("CLICOLOR_FORCE", "1")]
+        )
+        .color_depth,
+        ColorDepth::Indexed
+    );
+    assert_eq!(
+        detect(false, &[("CLICOLOR_FORCE", "0")]),
+        Capabilities::none()
+    );
+}
+
+#[test]
+fn clicolor() {
+    assert_eq!(detect(true, &[("CLICOLOR", "0")]), Capabilities::none());
+    assert_eq!(
+        detect(true, &[("CLICOLOR", "1")]).color_depth,
+        ColorDepth::Ansi
+    );
+    assert_eq!(
+        detect(true, &[("CLICOLOR", "0"), ("CLICOLOR_FORCE", "1")]).color_depth,
+        ColorDepth::Ansi
+    );
+}
+
+#[test]
+fn no_color() {
+    let capabilities = detect(
+        true,
+        &[
+            ("NO_COLOR", "1"),
+            ("COLORTERM", "truecolor"),
+            ("CLICOLOR_FORCE", "1"),
+        ],
+    );
+    assert_eq!(capabilities.color_depth, ColorDepth::NoColor);
+    assert!(capabilities.supports(Effect::Bold));
+
+    assert_eq!(
+        detect(true, &[("NO_COLOR", "")]).color_depth,
+        ColorDepth::Ansi
+    );
+}
+
+#[test]
+fn dumb_and_linux() {
+    assert_eq!(detect(true, &[("TERM", "dumb")]), Capabilities::none());
+
+    let linux = detect(true, &[("TERM", "linux")]);
+    assert_eq!(linux.color_depth, ColorDepth::Ansi);
+    assert!(linux.supports(Effect::Bold));
+    assert!(!linux.supports(Effect::Italic));
+}
+
+#[test]
+fn apply() {
+    let capabilities = Capabilities::new(ColorDepth::Ansi, Effect::Bold.into());
+    let s = StyledStr::plain("test")
+        .with(Color::Rgb { r: 0, g: 0, b: 200 })
+        .bold()
+        .italic();
+    assert_eq!(
+        capabilities.apply(s),
+        StyledStr::plain("test").with(AnsiColor::Blue.dark()).bold()
+    );
+}
+
+#[test]
+fn render_with() {
+    let s = StyledStr::plain("test").with(AnsiColor::Red.dark()).bold();
+    let mut output = Vec::new();
+    text_style::termion::render_with(&mut output, &s, &Capabilities::none())
+        .expect("Failed to render string");
+    assert_eq!(output, b"test");
+
+    let mut output = Vec::new();
+    let capabilities = Capabilities::new(ColorDepth::Ansi, Default::default());
+    text_style::ansi_term::render_iter_with(&mut output, vec![s], &capabilities)
+        .expect("Failed to render string");
+    assert_eq!(output, b"\x1b[31mtest\x1b[0m");
+}
+
+#[test]
+fn hyperlinks() {
+    let hyperlinks = |is_tty, vars| detect(is_tty, vars).hyperlinks;
+    assert!(hyperlinks(true, &[("TERM", "xterm-256color")]));
+    assert!(!hyperlinks(false, &[("TERM", "xterm-256color")]));
+    assert!(!hyperlinks(true, &[("TERM", "linux")]));
+    assert!(hyperlinks(false, &[("FORCE_HYPERLINK", "1")]));
+    assert!(!hyperlinks(true, &[("FORCE_HYPERLINK", "0")]));
+
+    let s = StyledStr::plain("test").link("https://example.com");
+    assert_eq!(Capabilities::all().apply(s.clone()), s);
+    assert_eq!(Capabilities::none().apply(s), StyledStr::plain("test"));
+}
@@ -0,0 +1,96 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+use text_style::theme::Theme;
+use text_style::{AnsiColor, Effect, Style, StyledStr, StyledString, StyledText};
+
+fn theme() -> Theme {
+    vec![
+        ("error", Style::fg(AnsiColor::Red.light())),
+        ("diagnostic.error", Style::effect(Effect::Bold)),
+        ("path", Style::effect(Effect::Underline)),
+    ]
+    .into_iter()
+    .collect()
+}
+
+#[test]
+fn fallback() {
+    let theme = theme();
+    assert_eq!(theme.get("error"), Some(Style::fg(AnsiColor::Red.light())));
+    assert_eq!(
+        theme.get("diagnostic.error"),
+        Some(Style::effect(Effect::Bold))
+    );
+    assert_eq!(
+        theme.get("compiler.diagnostic.error"),
+        Some(Style::effect(Effect::Bold))
+    );
+    assert_eq!(
+        theme.get("compiler.error"),
+        Some(Style::fg(AnsiColor::Red.light()))
+    );
+    assert_eq!(theme.get("warning"), None);
+    assert_eq!(theme.get("error."), None);
+    assert_eq!(theme.resolve("warning"), Style::default());
+}
+
+#[test]
+fn apply() {
+    let theme = theme();
+    assert_eq!(
+        theme.apply(StyledStr::themed("test", "path")),
+        StyledStr::themed("test", "path").underline()
+    );
+    assert_eq!(
+        theme.apply(StyledStr::themed("test", "path").bold()).style,
+        Some(Style::effects(
+            vec![Effect::Bold, Effect::Underline].into_iter().collect()
+        ))
+    );
+    assert_eq!(
+        theme.apply(StyledStr::themed("test", "warning").bold()),
+        StyledStr::themed("test", "warning").bold()
+    );
+    assert_eq!(theme.apply("test"), StyledStr::plain("test"));
+}
+
+#[test]
+fn apply_text() {
+    let theme = theme();
+    let mut text = StyledText::new();
+    text.push(StyledString::themed("error".to_owned(), "error"));
+    text.push(StyledString::plain(": ".to_owned()));
+    text.push(StyledString::themed("file.rs".to_owned(), "path"));
+
+    let text = theme.apply_text(text);
+    let styles: Vec<_> = text.iter().map(|s| s.style).collect();
+    assert_eq!(
+        styles,
+        vec![
+            Some(Style::fg(AnsiColor::Red.light())),
+            None,
+            Some(Style::effect(Effect::Underline)),
+        ]
+    );
+}
+
+#[test]
+fn render_themed() {
+    let theme = theme();
+    let v = [
+        StyledStr::themed("error", "error"),
+        StyledStr::plain(": "),
+        StyledStr::themed("file.rs", "path"),
+    ];
+
+    let mut output = Vec::new();
+    text_style::ansi::render_iter_themed(&mut output, v.iter(), &theme)
+        .expect("Failed to render string");
+    assert_eq!(output, b"\x1b[91merror\x1b[0m: \x1b[4mfile.rs\x1b[0m");
+
+    let mut output = Vec::new();
+    text_style::ansi_term::render_themed(&mut output, v[0].clone(), &theme)
+        .expect("Failed to render string");
+    assert_eq!(output, b"\x1b[38;5;9merror\x1b[0m");
+}
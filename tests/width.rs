@@ -0,0 +1,104 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+use text_style::{width, AnsiColor, StyledStr, StyledString, StyledText};
+
+#[test]
+fn str_width() {
+    assert_eq!(width::str_width(""), 0);
+    assert_eq!(width::str_width("abc"), 3);
+    assert_eq!(width::str_width("\x1b\n"), 0);
+    assert_eq!(width::str_width("中文"), 4);
+    assert_eq!(width::str_width("ｆｕｌｌ"), 8);
+    assert_eq!(width::str_width("a\u{301}\u{327}b"), 2);
+    assert_eq!(width::str_width("a\u{200b}b"), 2);
+    assert_eq!(width::str_width("🦀"), 2);
+    assert_eq!(width::str_width("👩\u{200d}🔬"), 2);
+}
+
+#[test]
+fn styled_width() {
+    let s = StyledStr::plain("日本").bold();
+    assert_eq!(s.width(), 4);
+    assert_eq!(StyledString::from(s.clone()).width(), 4);
+    let text: StyledText = vec![s, StyledStr::plain("x\n")].into_iter().collect();
+    assert_eq!(text.width(), 5);
+    assert_eq!(width::width(text.iter()), 5);
+}
+
+#[test]
+fn truncate_wide() {
+    let v = [StyledStr::plain("ab").bold(), StyledStr::plain("日本")];
+    // the wide character does not fit next to the ellipsis
+    assert_eq!(
+        width::truncate(v.iter(), 4, "…").spans(),
+        &[
+            StyledString::plain("ab".to_owned()).bold(),
+            StyledString::plain("…".to_owned()),
+        ]
+    );
+    assert_eq!(width::truncate(v.iter(), 5, "…").plain_text(), "ab日…");
+    assert_eq!(width::truncate(v.iter(), 6, "…").plain_text(), "ab日本");
+    assert_eq!(width::truncate(v.iter(), 4, "").plain_text(), "ab日");
+}
+
+#[test]
+fn truncate_graphemes() {
+    let v = [StyledStr::plain("e\u{301}e\u{301}e\u{301}")];
+    assert_eq!(width::truncate(v.iter(), 2, "…").plain_text(), "e\u{301}…");
+}
+
+#[test]
+fn truncate_ellipsis() {
+    let v = [StyledStr::plain("abcdef").with(AnsiColor::Red.dark())];
+    assert_eq!(
+        width::truncate(v.iter(), 4, StyledStr::plain("...").italic()).spans(),
+        &[
+            StyledString::plain("a".to_owned()).with(AnsiColor::Red.dark()),
+            StyledString::plain("...".to_owned()).italic(),
+        ]
+    );
+    assert_eq!(width::truncate(v.iter(), 2, "...").plain_text(), "..");
+    assert_eq!(width::truncate(v.iter(), 0, "...").plain_text(), "");
+}
+
+#[test]
+fn truncate_link() {
+    let v = [StyledStr::plain("abcdef").link("https://example.com")];
+    let text = width::truncate(v.iter(), 3, "…");
+    assert_eq!(
+        text.spans(),
+        &[
+            StyledString::plain("ab".to_owned()).link("https://example.com"),
+            StyledString::plain("…".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn expand_tabs() {
+    assert_eq!(width::expand_tabs("", 4, 0), "");
+    assert_eq!(width::expand_tabs("\tab\tc\t", 4, 0), "    ab  c   ");
+    assert_eq!(width::expand_tabs("abcd\te", 4, 0), "abcd    e");
+    assert_eq!(width::expand_tabs("a\tb", 4, 2), "a b");
+    assert_eq!(
+        width::expand_tabs("a\tb\n\tc\r\n\td", 3, 0),
+        "a  b\n   c\r\n   d"
+    );
+    assert_eq!(width::expand_tabs("\ta\tb", 1, 0), " a b");
+}
+
+#[test]
+fn expand_tabs_zero_width() {
+    assert_eq!(width::expand_tabs("\ta\t\tb\t", 0, 0), "ab");
+    assert_eq!(width::expand_tabs("a\tb", 0, 3), "ab");
+}
+
+#[test]
+fn expand_tabs_wide() {
+    assert_eq!(width::expand_tabs("日\tb", 4, 0), "日  b");
+    assert_eq!(width::expand_tabs("日本\tb", 4, 0), "日本    b");
+    assert_eq!(width::expand_tabs("é\tb", 4, 0), "é   b");
+    assert_eq!(width::expand_tabs("e\u{301}\tb", 4, 0), "e\u{301}   b");
+    assert_eq!(width::expand_tabs("🦀x\tb", 4, 0), "🦀x b");
+}
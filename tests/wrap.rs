@@ -0,0 +1,86 @@
+// SPDX-FileCopyrightText: 2021 Robin Krahl <robin.krahl@ireas.org>
+// SPDX-License-Identifier: Apache-2.0 or MIT
+
+use text_style::{wrap, AnsiColor, StyledStr, StyledString};
+
+fn wrap_plain(s: &str, options: &wrap::Options) -> Vec<String> {
+    wrap::wrap(std::iter::once(s), options)
+        .iter()
+        .map(|line| line.plain_text())
+        .collect()
+}
+
+#[test]
+fn words() {
+    let options = wrap::Options::new(10);
+    assert_eq!(wrap_plain("", &options), Vec::<String>::new());
+    assert_eq!(wrap_plain("   ", &options), Vec::<String>::new());
+    assert_eq!(
+        wrap_plain("The quick brown fox jumps over the lazy dog", &options),
+        vec!["The quick", "brown fox", "jumps over", "the lazy", "dog"]
+    );
+    assert_eq!(
+        wrap_plain("a    b\t c          d", &options),
+        vec!["a    b\t c", "d"]
+    );
+}
+
+#[test]
+fn newlines() {
+    let options = wrap::Options::new(6).subsequent_indent("> ");
+    assert_eq!(
+        wrap_plain("abc def\n\n  gh\n", &options),
+        vec!["abc", "> def", "> ", ">   gh"]
+    );
+}
+
+#[test]
+fn break_words() {
+    let options = wrap::Options::new(4);
+    assert_eq!(
+        wrap_plain("a abcdefghij b", &options),
+        vec!["a", "abcd", "efgh", "ij b"]
+    );
+    assert_eq!(
+        wrap_plain("日本語です", &options),
+        vec!["日本", "語で", "す"]
+    );
+    assert_eq!(
+        wrap_plain("日本語", &wrap::Options::new(1)),
+        vec!["日", "本", "語"]
+    );
+    assert_eq!(
+        wrap_plain("a abcdefghij b", &options.break_words(false)),
+        vec!["a", "abcdefghij", "b"]
+    );
+}
+
+#[test]
+fn styles() {
+    let v = [
+        StyledStr::plain("red and").with(AnsiColor::Red.dark()),
+        StyledStr::plain(" bold").bold(),
+        StyledStr::plain("!").link("https://example.com"),
+    ];
+    let options = wrap::Options::new(7).initial_indent(StyledStr::plain("* ").dim());
+    let lines = wrap::wrap(v.iter(), &options);
+    assert_eq!(lines.len(), 3);
+    assert_eq!(
+        lines[0].spans(),
+        &[
+            StyledString::plain("* ".to_owned()).dim(),
+            StyledString::plain("red".to_owned()).with(AnsiColor::Red.dark()),
+        ]
+    );
+    assert_eq!(
+        lines[1].spans(),
+        &[StyledString::plain("and".to_owned()).with(AnsiColor::Red.dark())]
+    );
+    assert_eq!(
+        lines[2].spans(),
+        &[
+            StyledString::plain("bold".to_owned()).bold(),
+            StyledString::plain("!".to_owned()).link("https://example.com"),
+        ]
+    );
+}